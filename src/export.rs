@@ -0,0 +1,307 @@
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use chrono::{Datelike, NaiveDate};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Excel only detects UTF-8 (and so Thai text) in a CSV that starts with a BOM.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+/// Leading characters that make a spreadsheet read a cell as a formula.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// Plain CSV. Excel opening it directly reads digit-only fields as numbers and
+    /// drops their leading zeros ("05" becomes 5); use `ExcelCsv` or `Xlsx` for Excel.
+    #[default]
+    Csv,
+    /// CSV whose digit-only fields are written as `="05"` formulas, which Excel shows
+    /// as text. Other tools see the formula, not the number.
+    ExcelCsv,
+    Jsonl,
+    Xlsx,
+    Parquet,
+    /// Arrow IPC file, also known as Feather v2.
+    Arrow,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv | ExportFormat::ExcelCsv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+            ExportFormat::Arrow => "application/vnd.apache.arrow.file",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv | ExportFormat::ExcelCsv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Arrow => "arrow",
+        }
+    }
+
+    /// Columnar formats need a fixed schema, so only the draw history offers them.
+    pub fn is_columnar(self) -> bool {
+        matches!(self, ExportFormat::Parquet | ExportFormat::Arrow)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Cell {
+    Empty,
+    /// Kept as text everywhere so numbers such as "05" keep their leading zeros.
+    Text(String),
+    Number(f64),
+    Date(NaiveDate),
+}
+
+/// `text` behind a `'` when a spreadsheet would run it as a formula. Exports can carry
+/// what users typed, such as rejected numbers, so no text cell is trusted.
+fn neutralize_formula(text: &str) -> Cow<'_, str> {
+    if text.starts_with(FORMULA_PREFIXES) {
+        Cow::Owned(format!("'{}", text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+impl Cell {
+    fn to_csv_field(&self, excel: bool) -> String {
+        match self {
+            Cell::Empty => String::new(),
+            Cell::Text(text)
+                if excel && !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                format!("=\"{}\"", text)
+            }
+            Cell::Text(text) => neutralize_formula(text).into_owned(),
+            Cell::Number(n) => n.to_string(),
+            Cell::Date(date) => date.to_string(),
+        }
+    }
+}
+
+/// A named table; one sheet of a workbook.
+pub struct Table {
+    /// At most 31 characters and none of `[]:*?/\`, as Excel requires of sheet names.
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+/// UTF-8 CSV with a BOM and a header row; `excel` keeps leading zeros as in
+/// [`ExportFormat::ExcelCsv`].
+pub fn to_csv(table: &Table, excel: bool) -> Result<Vec<u8>, String> {
+    let mut writer = csv::Writer::from_writer(UTF8_BOM.to_vec());
+    writer
+        .write_record(&table.headers)
+        .map_err(|e| e.to_string())?;
+    for row in &table.rows {
+        writer
+            .write_record(row.iter().map(|cell| cell.to_csv_field(excel)))
+            .map_err(|e| e.to_string())?;
+    }
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// One JSON document per line.
+pub fn to_jsonl<T: Serialize>(records: impl IntoIterator<Item = T>) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut out, &record).map_err(|e| e.to_string())?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+/// A workbook with one sheet per table, in order.
+pub fn to_xlsx(tables: &[Table]) -> Result<Vec<u8>, String> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    for table in tables {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(&table.name).map_err(|e| e.to_string())?;
+        for (col, header) in table.headers.iter().enumerate() {
+            sheet
+                .write_string_with_format(0, col as u16, header, &bold)
+                .map_err(|e| e.to_string())?;
+        }
+        for (i, row) in table.rows.iter().enumerate() {
+            let r = i as u32 + 1;
+            for (col, cell) in row.iter().enumerate() {
+                let col = col as u16;
+                match cell {
+                    Cell::Empty => continue,
+                    Cell::Text(text) => sheet.write_string(r, col, neutralize_formula(text)),
+                    Cell::Number(n) => sheet.write_number(r, col, *n),
+                    Cell::Date(date) => {
+                        let excel_date = ExcelDateTime::from_ymd(
+                            date.year() as u16,
+                            date.month() as u8,
+                            date.day() as u8,
+                        )
+                        .map_err(|e| e.to_string())?;
+                        sheet.write_datetime_with_format(r, col, &excel_date, &date_format)
+                    }
+                }
+                .map_err(|e| e.to_string())?;
+            }
+        }
+        sheet.autofit();
+    }
+    workbook.save_to_buffer().map_err(|e| e.to_string())
+}
+
+/// Every leaf of `value` as a dotted path ("a.b[2].c") and its cell; object keys in sorted order.
+pub fn flatten_json(value: &Value) -> Vec<(String, Cell)> {
+    fn walk(path: String, value: &Value, out: &mut Vec<(String, Cell)>) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let child_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    walk(child_path, child, out);
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    walk(format!("{}[{}]", path, i), child, out);
+                }
+            }
+            Value::Null => out.push((path, Cell::Empty)),
+            Value::Bool(b) => out.push((path, Cell::Text(b.to_string()))),
+            Value::Number(n) => out.push((path, n.as_f64().map_or(Cell::Empty, Cell::Number))),
+            Value::String(s) => out.push((path, Cell::Text(s.clone()))),
+        }
+    }
+    let mut out = Vec::new();
+    walk(String::new(), value, &mut out);
+    out
+}
+
+/// Parquet with Snappy-compressed columns.
+pub fn to_parquet(batch: &RecordBatch) -> Result<Vec<u8>, String> {
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut out = Vec::new();
+    let mut writer =
+        ArrowWriter::try_new(&mut out, batch.schema(), Some(props)).map_err(|e| e.to_string())?;
+    writer.write(batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+pub fn to_arrow_ipc(batch: &RecordBatch) -> Result<Vec<u8>, String> {
+    let mut writer = FileWriter::try_new(Vec::new(), &batch.schema()).map_err(|e| e.to_string())?;
+    writer.write(batch).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        Table {
+            name: "Draws".to_string(),
+            headers: vec!["Date".to_string(), "Number".to_string(), "Note".to_string()],
+            rows: vec![vec![
+                Cell::Date(NaiveDate::from_ymd_opt(2024, 1, 16).unwrap()),
+                Cell::Text("05".to_string()),
+                Cell::Text("123 456".to_string()),
+            ]],
+        }
+    }
+
+    #[test]
+    fn csv_starts_with_bom_and_keeps_text() {
+        let csv = to_csv(&table(), false).unwrap();
+        assert!(csv.starts_with(UTF8_BOM));
+        let text = String::from_utf8(csv[UTF8_BOM.len()..].to_vec()).unwrap();
+        assert_eq!(text, "Date,Number,Note\n2024-01-16,05,123 456\n");
+    }
+
+    #[test]
+    fn excel_csv_wraps_digit_only_text() {
+        let csv = to_csv(&table(), true).unwrap();
+        let text = String::from_utf8(csv[UTF8_BOM.len()..].to_vec()).unwrap();
+        assert_eq!(
+            text,
+            "Date,Number,Note\n2024-01-16,\"=\"\"05\"\"\",123 456\n"
+        );
+    }
+
+    #[test]
+    fn excel_csv_leaves_other_cells_alone() {
+        for cell in [
+            Cell::Empty,
+            Cell::Number(5.0),
+            Cell::Text(String::new()),
+            Cell::Text("-5".to_string()),
+        ] {
+            assert_eq!(cell.to_csv_field(true), cell.to_csv_field(false));
+        }
+    }
+
+    #[test]
+    fn csv_neutralizes_formulas() {
+        for excel in [false, true] {
+            assert_eq!(Cell::Text("=1+1".to_string()).to_csv_field(excel), "'=1+1");
+            assert_eq!(
+                Cell::Text("@SUM(A1)".to_string()).to_csv_field(excel),
+                "'@SUM(A1)"
+            );
+            assert_eq!(Cell::Text("1=1".to_string()).to_csv_field(excel), "1=1");
+        }
+    }
+
+    #[test]
+    fn jsonl_writes_one_line_per_record() {
+        let out = to_jsonl([serde_json::json!({"a": 1}), serde_json::json!({"a": 2})]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"a\":1}\n{\"a\":2}\n");
+    }
+
+    #[test]
+    fn flattens_nested_json_into_paths() {
+        let value = serde_json::json!({"b": [1, {"c": "05"}], "a": null, "d": true});
+        let paths: Vec<(String, String)> = flatten_json(&value)
+            .into_iter()
+            .map(|(path, cell)| (path, cell.to_csv_field(false)))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("a".to_string(), String::new()),
+                ("b[0]".to_string(), "1".to_string()),
+                ("b[1].c".to_string(), "05".to_string()),
+                ("d".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn xlsx_is_a_zip_workbook() {
+        let xlsx = to_xlsx(&[table()]).unwrap();
+        assert!(xlsx.starts_with(b"PK"));
+    }
+}
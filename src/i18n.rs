@@ -0,0 +1,453 @@
+use actix_web::HttpRequest;
+use chrono::NaiveDate;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Locale {
+    #[default]
+    Th,
+    En,
+}
+
+impl Locale {
+    fn from_tag(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "th" => Some(Locale::Th),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// The `lang` query parameter when it names a supported locale, otherwise the
+    /// best supported entry of `Accept-Language`, otherwise Thai.
+    pub fn from_request(req: &HttpRequest) -> Locale {
+        let from_query = req.query_string().split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == "lang").then(|| Locale::from_tag(value)).flatten()
+        });
+        from_query
+            .or_else(|| {
+                let header = req.headers().get("Accept-Language")?.to_str().ok()?;
+                let mut ranges: Vec<(f32, &str)> = header
+                    .split(',')
+                    .map(|range| {
+                        let mut parts = range.split(';');
+                        let tag = parts.next().unwrap_or("").trim();
+                        let quality = parts
+                            .find_map(|p| p.trim().strip_prefix("q="))
+                            .and_then(|q| q.parse().ok())
+                            .unwrap_or(1.0);
+                        (quality, tag)
+                    })
+                    .collect();
+                // Stable, so equal weights keep the client's order.
+                ranges.sort_by(|a, b| b.0.total_cmp(&a.0));
+                ranges
+                    .into_iter()
+                    .filter(|(quality, _)| *quality > 0.0)
+                    .find_map(|(_, tag)| Locale::from_tag(tag))
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Every user-facing message of the server, in both locales.
+#[derive(Clone, Debug)]
+pub enum Message {
+    ScraperAlreadyRunning,
+    InvalidLotteryType,
+    ScrapeStarted,
+    ScrapeStarting,
+    ScrapingPage {
+        url: String,
+    },
+    ScrapePageFailed {
+        url: String,
+        error: String,
+    },
+    ScrapeComplete,
+    NoStoredDraws,
+    NoDrawsSelected,
+    DrawNotFound {
+        date: NaiveDate,
+    },
+    ImportUnreadable {
+        error: String,
+    },
+    ColumnarExportUnsupported,
+    ExportFailed {
+        error: String,
+    },
+    InsufficientAnalysisData {
+        required: usize,
+        found: usize,
+    },
+    InvalidEntriesRejected {
+        count: usize,
+    },
+    DescriptiveStatsFailed,
+    /// The analysis stopped before finishing, e.g. its worker thread panicked.
+    AnalysisFailed {
+        error: String,
+    },
+    InvalidDigitSeries,
+    InsufficientDraws {
+        required: usize,
+        found: usize,
+    },
+    PermutationWidthUnsupported,
+    InvalidNumber {
+        number: String,
+        width: usize,
+    },
+    NoHotEndings,
+    NoTickets,
+    TooManyTickets {
+        max: usize,
+    },
+    InvalidTicketWidth,
+    InvalidDigitWidth {
+        max: usize,
+    },
+    DigitOutOfRange,
+    PositionOutOfRange {
+        position: usize,
+        width: usize,
+    },
+    InvalidEndings,
+    AllDigitsExcluded,
+    NoAllowedEndings,
+    ConstraintsUnsatisfiable,
+    InvalidTicketPrice,
+    TicketCountOutOfRange {
+        max: usize,
+    },
+    /// A tier other than the first prize, front-3, last-3 or last-2.
+    UnsupportedTier,
+    /// Every stored draw predates the oldest known prize table.
+    NoDrawsInPrizeTablePeriod,
+    InsufficientPredictionHistory {
+        required: usize,
+    },
+    TooManyWheelTargets {
+        max: usize,
+    },
+    NoWheelTargets,
+    NotEnoughDistinctDigits {
+        required: usize,
+    },
+    Methodology {
+        prior_alpha: f64,
+    },
+    StatisticalEvidence {
+        prediction: Option<String>,
+        probability: f64,
+        std_dev: f64,
+    },
+    PredictionLogic,
+    UncertaintyAnalysis {
+        sample_size: usize,
+        uniform_probability: f64,
+        max_shift: f64,
+        kl_divergence: f64,
+        credible_mass: f64,
+    },
+}
+
+impl Message {
+    pub fn text(&self, locale: Locale) -> String {
+        use Locale::{En, Th};
+        match (self, locale) {
+            (Message::ScraperAlreadyRunning, Th) => "กำลังดึงข้อมูลอยู่แล้ว".to_string(),
+            (Message::ScraperAlreadyRunning, En) => "A scraper is already running.".to_string(),
+            (Message::InvalidLotteryType, Th) => "ประเภทสลากไม่ถูกต้อง".to_string(),
+            (Message::InvalidLotteryType, En) => "Invalid lottery type.".to_string(),
+            (Message::ScrapeStarted, Th) => "เริ่มดึงข้อมูลแล้ว!".to_string(),
+            (Message::ScrapeStarted, En) => "Scraping process started!".to_string(),
+            (Message::ScrapeStarting, Th) => "🚀 เริ่มดึงข้อมูลสลากกินแบ่งรัฐบาล...".to_string(),
+            (Message::ScrapeStarting, En) => "🚀 Starting scraper for Thai Lottery...".to_string(),
+            (Message::ScrapingPage { url }, Th) => format!("📄 กำลังดึงหน้า: {}", url),
+            (Message::ScrapingPage { url }, En) => format!("📄 Scraping page: {}", url),
+            (Message::ScrapePageFailed { url, error }, Th) => {
+                format!("⚠️ ดึงหน้า {} ไม่สำเร็จ: {}", url, error)
+            }
+            (Message::ScrapePageFailed { url, error }, En) => {
+                format!("⚠️ Error scraping page {}: {}", url, error)
+            }
+            (Message::ScrapeComplete, Th) => "✅ ดึงข้อมูลสลากกินแบ่งรัฐบาลเสร็จสิ้น".to_string(),
+            (Message::ScrapeComplete, En) => "✅ Thai Lottery scraping complete.".to_string(),
+            (Message::NoStoredDraws, Th) => "ยังไม่มีข้อมูลผลรางวัล กรุณาดึงข้อมูลก่อน".to_string(),
+            (Message::NoStoredDraws, En) => {
+                "No draw history is stored yet; run the scraper first.".to_string()
+            }
+            (Message::NoDrawsSelected, Th) => "ไม่พบผลรางวัลตามเงื่อนไขที่เลือก".to_string(),
+            (Message::NoDrawsSelected, En) => "No draws match the selection.".to_string(),
+            (Message::DrawNotFound { date }, Th) => format!("ไม่พบผลรางวัลงวดวันที่ {}", date),
+            (Message::DrawNotFound { date }, En) => format!("No draw found for {}.", date),
+            (Message::ImportUnreadable { error }, Th) => format!("อ่านไฟล์นำเข้าไม่สำเร็จ: {}", error),
+            (Message::ImportUnreadable { error }, En) => {
+                format!("The uploaded file could not be read: {}", error)
+            }
+            (Message::ColumnarExportUnsupported, Th) => {
+                "ไฟล์ Parquet และ Arrow ส่งออกได้เฉพาะประวัติผลรางวัล".to_string()
+            }
+            (Message::ColumnarExportUnsupported, En) => {
+                "Parquet and Arrow exports are only available for the draw history.".to_string()
+            }
+            (Message::ExportFailed { error }, Th) => format!("สร้างไฟล์ส่งออกไม่สำเร็จ: {}", error),
+            (Message::ExportFailed { error }, En) => format!("The export could not be created: {}", error),
+            (Message::InsufficientAnalysisData { required, found }, Th) => format!(
+                "ข้อมูลไม่เพียงพอ AI ต้องการชุดตัวเลขที่ถูกต้องอย่างน้อย {} ชุด แต่พบเพียง {} ชุด",
+                required, found
+            ),
+            (Message::InsufficientAnalysisData { required, found }, En) => format!(
+                "Not enough data: the analysis needs at least {} valid numbers but only {} were found.",
+                required, found
+            ),
+            (Message::InvalidEntriesRejected { count }, Th) => {
+                format!("พบข้อมูลที่ไม่ถูกต้อง {} รายการ", count)
+            }
+            (Message::InvalidEntriesRejected { count }, En) => {
+                format!("{} invalid entries were rejected.", count)
+            }
+            (Message::AnalysisFailed { error }, Th) => format!("การวิเคราะห์ล้มเหลว: {}", error),
+            (Message::AnalysisFailed { error }, En) => format!("The analysis failed: {}", error),
+            (Message::DescriptiveStatsFailed, Th) => "ไม่สามารถคำนวณค่าสถิติเชิงพรรณนาได้".to_string(),
+            (Message::DescriptiveStatsFailed, En) => {
+                "Descriptive statistics could not be computed.".to_string()
+            }
+            (Message::InvalidDigitSeries, Th) => {
+                "ข้อมูลต้องเป็นตัวเลข 0-9 ที่มีจำนวนหลักเท่ากัน".to_string()
+            }
+            (Message::InvalidDigitSeries, En) => {
+                "The numbers must consist of digits 0-9 and share the same width.".to_string()
+            }
+            (Message::InsufficientDraws { required, found }, Th) => format!(
+                "ข้อมูลไม่เพียงพอ การวิเคราะห์นี้ต้องการผลรางวัลอย่างน้อย {} งวด แต่พบเพียง {} งวด",
+                required, found
+            ),
+            (Message::InsufficientDraws { required, found }, En) => format!(
+                "Not enough data: the analysis needs at least {} draws but only {} were found.",
+                required, found
+            ),
+            (Message::PermutationWidthUnsupported, Th) => {
+                "การกลับเลขรองรับเฉพาะเลข 2, 3 และ 6 หลัก".to_string()
+            }
+            (Message::PermutationWidthUnsupported, En) => {
+                "Permutations are only available for 2-, 3- and 6-digit numbers.".to_string()
+            }
+            (Message::InvalidNumber { number, width }, Th) => {
+                format!("เลข '{}' ต้องเป็นตัวเลข {} หลัก", number, width)
+            }
+            (Message::InvalidNumber { number, width }, En) => {
+                format!("'{}' must be a {}-digit number.", number, width)
+            }
+            (Message::NoHotEndings, Th) => "ไม่พบเลขท้าย 2 ตัวที่เป็นเลขร้อนในช่วงที่เลือก".to_string(),
+            (Message::NoHotEndings, En) => {
+                "No two-digit ending is hot in the selected window.".to_string()
+            }
+            (Message::NoTickets, Th) => "กรุณาระบุเลขสลากอย่างน้อย 1 ใบ".to_string(),
+            (Message::NoTickets, En) => "Enter at least one ticket number.".to_string(),
+            (Message::TooManyTickets { max }, Th) => format!("ตรวจได้ครั้งละไม่เกิน {} ใบ", max),
+            (Message::TooManyTickets { max }, En) => {
+                format!("At most {} tickets can be checked at once.", max)
+            }
+            (Message::InvalidTicketWidth, Th) => "จำนวนหลักต้องอยู่ระหว่าง 1 ถึง 6".to_string(),
+            (Message::InvalidTicketWidth, En) => {
+                "The number of digits must be between 1 and 6.".to_string()
+            }
+            (Message::InvalidDigitWidth { max }, Th) => {
+                format!("ความยาวของเลข (digit_width) ต้องอยู่ระหว่าง 1 ถึง {} หลัก", max)
+            }
+            (Message::InvalidDigitWidth { max }, En) => {
+                format!("The digit width must be between 1 and {}.", max)
+            }
+            (Message::DigitOutOfRange, Th) => "ตัวเลขที่กำหนดต้องอยู่ระหว่าง 0 ถึง 9".to_string(),
+            (Message::DigitOutOfRange, En) => "Digits must be between 0 and 9.".to_string(),
+            (Message::PositionOutOfRange { position, width }, Th) => {
+                format!("ตำแหน่งที่ {} อยู่นอกช่วง 1 ถึง {}", position, width)
+            }
+            (Message::PositionOutOfRange { position, width }, En) => {
+                format!("Position {} is outside 1 to {}.", position, width)
+            }
+            (Message::InvalidEndings, Th) => {
+                "เลขท้ายที่กำหนดต้องเป็นเลข 2 หลัก และตั๋วต้องมีอย่างน้อย 2 หลัก".to_string()
+            }
+            (Message::InvalidEndings, En) => {
+                "Endings must be two-digit numbers, on tickets of at least two digits.".to_string()
+            }
+            (Message::AllDigitsExcluded, Th) => {
+                "เงื่อนไขตัดตัวเลขออกทั้งหมด ไม่สามารถสุ่มเลขได้".to_string()
+            }
+            (Message::AllDigitsExcluded, En) => {
+                "The constraints exclude every digit at some position.".to_string()
+            }
+            (Message::NoAllowedEndings, Th) => {
+                "ไม่มีเลขท้ายที่กำหนดตรงกับเงื่อนไขตำแหน่งหรือตัวเลขที่ตัดออก".to_string()
+            }
+            (Message::NoAllowedEndings, En) => {
+                "None of the endings fits the required positions or excluded digits.".to_string()
+            }
+            (Message::ConstraintsUnsatisfiable, Th) => {
+                "ไม่พบเลขที่ตรงตามเงื่อนไขทั้งหมด กรุณาผ่อนคลายเงื่อนไข".to_string()
+            }
+            (Message::ConstraintsUnsatisfiable, En) => {
+                "No number satisfies every constraint; please relax them.".to_string()
+            }
+            (Message::InvalidTicketPrice, Th) => "ราคาสลากต้องมากกว่า 0".to_string(),
+            (Message::InvalidTicketPrice, En) => "The ticket price must be above 0.".to_string(),
+            (Message::TicketCountOutOfRange { max }, Th) => {
+                format!("จำนวนสลากต่องวดต้องอยู่ระหว่าง 1 ถึง {}", max)
+            }
+            (Message::TicketCountOutOfRange { max }, En) => {
+                format!("Tickets per draw must be between 1 and {}.", max)
+            }
+            (Message::UnsupportedTier, Th) => {
+                "รองรับเฉพาะรางวัลที่ 1 เลขหน้า 3 ตัว เลขท้าย 3 ตัว และเลขท้าย 2 ตัว".to_string()
+            }
+            (Message::UnsupportedTier, En) => {
+                "Only the first prize, front 3, last 3 and last 2 digits are supported.".to_string()
+            }
+            (Message::NoDrawsInPrizeTablePeriod, Th) => {
+                "ไม่มีงวดที่บันทึกไว้อยู่ในช่วงที่ตารางเงินรางวัลมีผลบังคับใช้".to_string()
+            }
+            (Message::NoDrawsInPrizeTablePeriod, En) => {
+                "No stored draws fall within the prize table's validity period.".to_string()
+            }
+            (Message::InsufficientPredictionHistory { required }, Th) => format!(
+                "ข้อมูลไม่เพียงพอ กลยุทธ์ตามการทำนายต้องการประวัติอย่างน้อย {} ชุดก่อนเริ่มเล่น",
+                required
+            ),
+            (Message::InsufficientPredictionHistory { required }, En) => format!(
+                "Not enough data: the predicted strategy needs at least {} past numbers before it plays.",
+                required
+            ),
+            (Message::TooManyWheelTargets { max }, Th) => format!(
+                "เงื่อนไขนี้มีเลขที่ต้องครอบคลุมมากกว่า {} ตัว กรุณาลดจำนวนเลข",
+                max
+            ),
+            (Message::TooManyWheelTargets { max }, En) => format!(
+                "These targets expand to more than {} numbers; please use fewer.",
+                max
+            ),
+            (Message::NoWheelTargets, Th) => {
+                "กรุณาระบุเลขที่ต้องการให้ครอบคลุมอย่างน้อย 1 ตัว".to_string()
+            }
+            (Message::NoWheelTargets, En) => "Enter at least one number to cover.".to_string(),
+            (Message::NotEnoughDistinctDigits { required }, Th) => {
+                format!("ต้องระบุตัวเลขที่ไม่ซ้ำกันอย่างน้อย {} ตัว", required)
+            }
+            (Message::NotEnoughDistinctDigits { required }, En) => {
+                format!("Enter at least {} distinct digits.", required)
+            }
+            (Message::Methodology { prior_alpha }, Th) => format!(
+                "ใช้การประมาณแบบเบย์ (Bayesian) ด้วยการแจกแจง Dirichlet-Multinomial โดยเริ่มจากความเชื่อก่อนหน้าแบบสม่ำเสมอ (α = {}) แล้วปรับด้วยความถี่ที่พบจริงในแต่ละหลักและเลขท้าย 2 ตัว",
+                prior_alpha
+            ),
+            (Message::Methodology { prior_alpha }, En) => format!(
+                "Bayesian estimation with a Dirichlet-multinomial model: a uniform prior (α = {}) is updated with the observed frequencies of each digit position and of the two-digit ending.",
+                prior_alpha
+            ),
+            (Message::StatisticalEvidence { prediction, probability, std_dev }, Th) => format!(
+                "ตัวเลข '{}' มีค่าความน่าจะเป็นภายหลัง (posterior) สูงสุดที่ {:.4}% ค่าเบี่ยงเบนมาตรฐานของข้อมูลคือ {:.2}",
+                prediction.as_deref().unwrap_or("N/A"),
+                probability * 100.0,
+                std_dev
+            ),
+            (Message::StatisticalEvidence { prediction, probability, std_dev }, En) => format!(
+                "'{}' has the highest posterior probability at {:.4}%. The standard deviation of the data is {:.2}.",
+                prediction.as_deref().unwrap_or("N/A"),
+                probability * 100.0,
+                std_dev
+            ),
+            (Message::PredictionLogic, Th) => "การทำนายหลักคือตัวเลขที่มีค่าเฉลี่ยภายหลัง (posterior mean) สูงสุด สำหรับเลข 2 ตัวคำนวณจากทั้งจำนวน ส่วนเลขที่ยาวกว่านั้นคำนวณจากแต่ละหลักแยกกัน ตัวเลือกสำรองคือลำดับถัดมา".to_string(),
+            (Message::PredictionLogic, En) => "The main prediction is the number with the highest posterior mean. Two-digit numbers are scored as a whole; longer numbers digit by digit. The alternatives are the next best.".to_string(),
+            (
+                Message::UncertaintyAnalysis {
+                    sample_size,
+                    uniform_probability,
+                    max_shift,
+                    kl_divergence,
+                    credible_mass,
+                },
+                Th,
+            ) => format!(
+                "ข้อมูล {} ชุดทำให้ความน่าจะเป็นภายหลังต่างจากค่าสม่ำเสมอ ({:.2}%) มากที่สุดเพียง {:.2} จุดเปอร์เซ็นต์ (KL divergence = {:.5}) หากช่วงความเชื่อมั่น {:.0}% ของตัวเลขใดครอบคลุมค่าสม่ำเสมอ แสดงว่าข้อมูลยังไม่มีหลักฐานเพียงพอว่าตัวเลขนั้นมีโอกาสออกมากกว่าปกติ",
+                sample_size,
+                uniform_probability * 100.0,
+                max_shift * 100.0,
+                kl_divergence,
+                credible_mass * 100.0
+            ),
+            (
+                Message::UncertaintyAnalysis {
+                    sample_size,
+                    uniform_probability,
+                    max_shift,
+                    kl_divergence,
+                    credible_mass,
+                },
+                En,
+            ) => format!(
+                "With {} draws the posterior moves at most {:.2} percentage points away from uniform ({:.2}%) (KL divergence = {:.5}). If a number's {:.0}% credible interval contains the uniform probability, the data gives no real evidence that it is drawn more often than chance.",
+                sample_size,
+                max_shift * 100.0,
+                uniform_probability * 100.0,
+                kl_divergence,
+                credible_mass * 100.0
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn query_parameter_wins_over_header() {
+        let req = TestRequest::with_uri("/status?lang=en")
+            .insert_header(("Accept-Language", "th"))
+            .to_http_request();
+        assert_eq!(Locale::from_request(&req), Locale::En);
+    }
+
+    #[test]
+    fn header_is_read_by_quality() {
+        let req = TestRequest::default()
+            .insert_header(("Accept-Language", "fr;q=1.0, th;q=0.5, en-US;q=0.8"))
+            .to_http_request();
+        assert_eq!(Locale::from_request(&req), Locale::En);
+        let req = TestRequest::default()
+            .insert_header(("Accept-Language", "en;q=0, th"))
+            .to_http_request();
+        assert_eq!(Locale::from_request(&req), Locale::Th);
+    }
+
+    #[test]
+    fn defaults_to_thai() {
+        let req = TestRequest::with_uri("/status?lang=fr").to_http_request();
+        assert_eq!(Locale::from_request(&req), Locale::Th);
+    }
+
+    #[test]
+    fn messages_differ_between_locales() {
+        let messages = [
+            Message::NoStoredDraws,
+            Message::InvalidNumber {
+                number: "12a".to_string(),
+                width: 2,
+            },
+            Message::TooManyWheelTargets { max: 1000 },
+            Message::ExportFailed {
+                error: "disk full".to_string(),
+            },
+        ];
+        for message in messages {
+            let (th, en) = (message.text(Locale::Th), message.text(Locale::En));
+            assert!(!th.is_empty() && !en.is_empty());
+            assert_ne!(th, en);
+        }
+    }
+}
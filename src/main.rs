@@ -1,34 +1,133 @@
 use actix_files::Files;
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use chrono::{Datelike, NaiveDate};
 use lazy_static::lazy_static;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
-use statrs::statistics::{Data, Distribution, Median, Min, Max};
-use statrs::distribution::Normal;
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Mutex;
 use tokio::time::{sleep, Duration};
 
+mod export;
+mod i18n;
+mod stats;
+mod tickets;
+mod validation;
+
+use i18n::{Locale, Message};
+
 // --- Data Structures ---
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 struct ThaiLottoResult {
+    /// `None` when the page did not carry a parseable date.
     #[serde(rename = "Draw Date")]
-    draw_date: String,
+    draw_date: Option<NaiveDate>,
     #[serde(rename = "First Prize")]
     first_prize: String,
+    #[serde(rename = "Front 3 Digits", default)]
+    front_3_digits: Vec<String>,
+    #[serde(rename = "Last 3 Digits", default)]
+    last_3_digits: Vec<String>,
     #[serde(rename = "Last 2 Digits")]
     last_2_digits: String,
+    /// Lower prizes; empty when the source page does not list them.
+    #[serde(rename = "2nd Prizes", default)]
+    second_prizes: Vec<String>,
+    #[serde(rename = "3rd Prizes", default)]
+    third_prizes: Vec<String>,
+    #[serde(rename = "4th Prizes", default)]
+    fourth_prizes: Vec<String>,
+    #[serde(rename = "5th Prizes", default)]
+    fifth_prizes: Vec<String>,
+}
+
+/// Column names of the prize tiers, matching the serialized field names.
+const PRIZE_TIER_COLUMNS: [&str; 8] = ["First Prize", "Front 3 Digits", "Last 3 Digits", "Last 2 Digits", "2nd Prizes", "3rd Prizes", "4th Prizes", "5th Prizes"];
+/// The same tiers as snake_case names, for columnar exports.
+const PRIZE_TIER_FIELDS: [&str; 8] = ["first_prize", "front_3_digits", "last_3_digits", "last_2_digits", "second_prizes", "third_prizes", "fourth_prizes", "fifth_prizes"];
+
+#[derive(Deserialize, Clone, Copy)]
+enum PrizeField {
+    #[serde(rename = "first_prize")]
+    FirstPrize,
+    #[serde(rename = "front_3_digits")]
+    Front3Digits,
+    #[serde(rename = "last_3_digits")]
+    Last3Digits,
+    #[serde(rename = "last_2_digits")]
+    Last2Digits,
+}
+
+impl PrizeField {
+    const ALL: [PrizeField; 4] = [PrizeField::FirstPrize, PrizeField::Front3Digits, PrizeField::Last3Digits, PrizeField::Last2Digits];
+}
+
+impl ThaiLottoResult {
+    fn numbers(&self, field: PrizeField) -> Vec<String> {
+        match field {
+            PrizeField::FirstPrize => vec![self.first_prize.clone()],
+            PrizeField::Front3Digits => self.front_3_digits.clone(),
+            PrizeField::Last3Digits => self.last_3_digits.clone(),
+            PrizeField::Last2Digits => vec![self.last_2_digits.clone()],
+        }
+    }
+
+    /// Inclusive bounds; an undated draw only passes when neither bound is set.
+    fn drawn_within(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> bool {
+        match self.draw_date {
+            Some(date) => from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to),
+            None => from.is_none() && to.is_none(),
+        }
+    }
+
+    /// Winning numbers of every tier, in [`PRIZE_TIER_COLUMNS`] order.
+    fn prize_tiers(&self) -> [Vec<String>; 8] {
+        [
+            vec![self.first_prize.clone()],
+            self.front_3_digits.clone(),
+            self.last_3_digits.clone(),
+            vec![self.last_2_digits.clone()],
+            self.second_prizes.clone(),
+            self.third_prizes.clone(),
+            self.fourth_prizes.clone(),
+            self.fifth_prizes.clone(),
+        ]
+    }
+
+    fn draw_numbers(&self) -> tickets::checker::DrawNumbers {
+        use tickets::prizes::PrizeTier;
+        let numbers = [
+            (PrizeTier::FirstPrize, vec![self.first_prize.clone()]),
+            (PrizeTier::SecondPrize, self.second_prizes.clone()),
+            (PrizeTier::ThirdPrize, self.third_prizes.clone()),
+            (PrizeTier::FourthPrize, self.fourth_prizes.clone()),
+            (PrizeTier::FifthPrize, self.fifth_prizes.clone()),
+            (PrizeTier::Front3, self.front_3_digits.clone()),
+            (PrizeTier::Last3, self.last_3_digits.clone()),
+            (PrizeTier::Last2, vec![self.last_2_digits.clone()]),
+        ];
+        tickets::checker::DrawNumbers { draw_date: self.draw_date, numbers: numbers.into_iter().collect() }
+    }
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Clone)]
 struct TaskStatus {
     is_running: bool,
     lotto_type: Option<String>,
-    progress: Vec<String>,
+    /// Rendered in the locale of whoever polls `/status`.
+    progress: Vec<Message>,
     results: Vec<ThaiLottoResult>,
 }
 
+#[derive(Serialize)]
+struct StatusResponse<'a> {
+    is_running: bool,
+    lotto_type: &'a Option<String>,
+    progress: Vec<String>,
+    results: &'a [ThaiLottoResult],
+}
+
 impl TaskStatus {
     fn new() -> Self {
         TaskStatus {
@@ -46,13 +145,23 @@ lazy_static! {
 
 // --- Web Scraper ---
 
+/// Parses the `datetime` attribute of a draw, e.g. "2024-06-16" or "2024-06-16T00:00:00+07:00".
+fn parse_draw_date(datetime: &str) -> Option<NaiveDate> {
+    to_gregorian(NaiveDate::parse_from_str(datetime.get(..10)?, "%Y-%m-%d").ok()?)
+}
+
+/// Converts Buddhist-era years (พ.ศ.) to the Gregorian calendar.
+fn to_gregorian(date: NaiveDate) -> Option<NaiveDate> {
+    if date.year() > 2400 { date.with_year(date.year() - stats::calendar::BUDDHIST_ERA_OFFSET) } else { Some(date) }
+}
+
 async fn scrape_thai_lotto_page(
     client: &reqwest::Client,
     url: &str,
 ) -> Result<(Vec<ThaiLottoResult>, Option<String>), String> {
     let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
     if !resp.status().is_success() {
-        return Err(format!("Request failed with status: {}", resp.status()));
+        return Err(format!("HTTP {}", resp.status()));
     }
     let body = resp.text().await.map_err(|e| e.to_string())?;
     let document = Html::parse_document(&body);
@@ -70,22 +179,31 @@ async fn scrape_thai_lotto_page(
             .select(&date_selector)
             .next()
             .and_then(|time| time.value().attr("datetime"))
-            .unwrap_or("Unknown")
-            .to_string();
+            .and_then(parse_draw_date);
 
         let mut first_prize = None;
+        let mut front_3_digits = Vec::new();
+        let mut last_3_digits = Vec::new();
         let mut last_2_digits = None;
+        let mut lower_prizes: [Vec<String>; 4] = Default::default();
 
         for li in article.select(&li_selector) {
             let label = li.select(&label_selector).next().map(|em| em.text().collect::<String>());
             let prize = li.select(&number_selector).next().map(|s| s.text().collect::<String>());
+            // 3-digit prizes list two numbers, either in separate tags or space-separated in one.
+            let prize_numbers: Vec<String> = li.select(&number_selector).flat_map(|s| s.text().collect::<String>().split_whitespace().map(str::to_string).collect::<Vec<_>>()).collect();
             if let (Some(label_text), Some(prize_text)) = (label, prize) {
+                if label_text.contains("ข้างเคียง") { continue; }
                 if label_text.contains("รางวัลที่ 1") { first_prize = Some(prize_text.trim().to_string()); }
+                else if label_text.contains("เลขหน้า 3 ตัว") { front_3_digits = prize_numbers; }
+                else if label_text.contains("เลขท้าย 3 ตัว") { last_3_digits = prize_numbers; }
                 else if label_text.contains("เลขท้าย 2 ตัว") { last_2_digits = Some(prize_text.trim().to_string()); }
+                else if let Some(i) = ["รางวัลที่ 2", "รางวัลที่ 3", "รางวัลที่ 4", "รางวัลที่ 5"].iter().position(|l| label_text.contains(l)) { lower_prizes[i] = prize_numbers; }
             }
         }
         if let (Some(fp), Some(l2d)) = (first_prize, last_2_digits) {
-            page_results.push(ThaiLottoResult { draw_date, first_prize: fp, last_2_digits: l2d });
+            let [second_prizes, third_prizes, fourth_prizes, fifth_prizes] = lower_prizes;
+            page_results.push(ThaiLottoResult { draw_date, first_prize: fp, front_3_digits, last_3_digits, last_2_digits: l2d, second_prizes, third_prizes, fourth_prizes, fifth_prizes });
         }
     }
     let next_page_url = document.select(&next_button_selector).next().and_then(|a| a.value().attr("href")).map(|s| s.to_string());
@@ -99,16 +217,24 @@ async fn run_scraper() {
     let mut current_url = Some(start_url);
 
     while let Some(url) = current_url {
-        { TASK_STATUS.lock().unwrap().progress.push(format!("📄 Scraping page: {}", url)); }
+        { TASK_STATUS.lock().unwrap().progress.push(Message::ScrapingPage { url: url.clone() }); }
         match scrape_thai_lotto_page(&client, &url).await {
             Ok((mut page_results, next_url)) => { all_results.append(&mut page_results); current_url = next_url; },
-            Err(e) => { TASK_STATUS.lock().unwrap().progress.push(format!("⚠️ Error scraping page {}: {}", url, e)); current_url = None; }
+            Err(e) => { TASK_STATUS.lock().unwrap().progress.push(Message::ScrapePageFailed { url: url.clone(), error: e }); current_url = None; }
         }
         sleep(Duration::from_millis(500)).await;
     }
     let mut status = TASK_STATUS.lock().unwrap();
+    // Scraped draws replace stored ones of the same date; everything else stored, such as
+    // imported history, is kept.
+    let scraped: HashSet<NaiveDate> = all_results.iter().filter_map(|d| d.draw_date).collect();
+    let kept: Vec<ThaiLottoResult> = status.results.drain(..).filter(|d| d.draw_date.is_some_and(|date| !scraped.contains(&date))).collect();
+    if !kept.is_empty() {
+        all_results.extend(kept);
+        all_results.sort_by_key(|d| std::cmp::Reverse(d.draw_date));
+    }
     status.results = all_results;
-    status.progress.push("✅ Thai Lottery scraping complete.".to_string());
+    status.progress.push(Message::ScrapeComplete);
     status.is_running = false;
 }
 
@@ -117,97 +243,310 @@ async fn run_scraper() {
 #[derive(Deserialize)]
 struct AnalyzeRequest {
     numbers: Vec<String>,
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+fn default_lotto_type() -> String {
+    "thai".to_string()
+}
+
+/// Picks numbers out of the stored draw history instead of taking them from the client.
+#[derive(Deserialize)]
+struct DrawSelector {
+    #[serde(default = "default_lotto_type")]
+    lotto_type: String,
+    field: PrizeField,
+    /// Inclusive; see [`ThaiLottoResult::drawn_within`].
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+impl DrawSelector {
+    /// Numbers of `field` from the draws in range, oldest first.
+    fn select(&self, results: &[ThaiLottoResult]) -> Vec<String> {
+        // Scraped results are newest first.
+        results.iter().rev()
+            .filter(|r| r.drawn_within(self.from, self.to))
+            .flat_map(|r| r.numbers(self.field))
+            .map(|n| n.trim().to_string())
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct AnalyzeHistoryRequest {
+    #[serde(flatten)]
+    selector: DrawSelector,
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+const DEFAULT_DRAWS_PER_PAGE: usize = 50;
+const MAX_DRAWS_PER_PAGE: usize = 500;
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum DrawSort {
+    #[default]
+    DateDesc,
+    DateAsc,
+}
+
+#[derive(Deserialize)]
+struct DrawsQuery {
+    #[serde(default = "default_lotto_type")]
+    lotto_type: String,
+    /// Inclusive; see [`ThaiLottoResult::drawn_within`].
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    /// Field that `contains` and `ends_with` look at; any of them when omitted.
+    field: Option<PrizeField>,
+    contains: Option<String>,
+    ends_with: Option<String>,
+    #[serde(default)]
+    sort: DrawSort,
+    /// 1-based.
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+impl DrawsQuery {
+    /// Matching stored draws in the requested order, before pagination.
+    fn select<'a>(&self, results: &'a [ThaiLottoResult]) -> Vec<&'a ThaiLottoResult> {
+        let mut draws: Vec<&ThaiLottoResult> = results.iter().filter(|d| self.matches(d)).collect();
+        match self.sort {
+            DrawSort::DateDesc => draws.sort_by_key(|d| std::cmp::Reverse(d.draw_date)),
+            DrawSort::DateAsc => draws.sort_by_key(|d| d.draw_date),
+        }
+        draws
+    }
+
+    fn matches(&self, draw: &ThaiLottoResult) -> bool {
+        if !draw.drawn_within(self.from, self.to) { return false; }
+        if self.contains.is_none() && self.ends_with.is_none() { return true; }
+        let fields = match self.field { Some(field) => vec![field], None => PrizeField::ALL.to_vec() };
+        fields.into_iter().flat_map(|f| draw.numbers(f)).any(|n| {
+            let n = n.trim();
+            self.contains.as_deref().is_none_or(|c| n.contains(c)) && self.ends_with.as_deref().is_none_or(|e| n.ends_with(e))
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct DrawPage<'a> {
+    /// Matching draws across all pages.
+    total: usize,
+    page: usize,
+    per_page: usize,
+    draws: Vec<&'a ThaiLottoResult>,
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    #[serde(default)]
+    format: export::ExportFormat,
+}
+
+#[derive(Deserialize)]
+struct PermutationRequest {
+    numbers: Vec<String>,
+    /// Number to expand; the model's top prediction when omitted.
+    prediction: Option<String>,
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+#[derive(Deserialize)]
+struct HotColdRequest {
+    numbers: Vec<String>,
+    /// Window sizes in draws; 0 means all draws.
+    #[serde(default)]
+    windows: Vec<usize>,
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+#[derive(Deserialize)]
+struct MonteCarloRequest {
+    numbers: Vec<String>,
+    /// Statistics to simulate; all of them when empty.
+    #[serde(default)]
+    statistics: Vec<stats::monte_carlo::SimulatedStatistic>,
+    simulations: Option<usize>,
+    /// Fixed seed for reproducible results.
+    seed: Option<u64>,
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+#[derive(Deserialize)]
+struct GenerateRequest {
+    /// Ticket width in digits (6 for a full ticket, 2 or 3 for the ending prizes).
+    digits: Option<usize>,
+    count: Option<usize>,
+    seed: Option<u64>,
+    #[serde(flatten)]
+    constraints: tickets::quick_pick::QuickPickConstraints,
+    /// Restricts endings to the hot last-2 numbers of `numbers` over `hot_window` draws;
+    /// with `allowed_endings`, to those of them that are hot.
+    #[serde(default)]
+    must_end_in_hot: bool,
+    /// Draw history, oldest first; required by `must_end_in_hot`.
+    #[serde(default)]
+    numbers: Vec<String>,
+    /// Recent draws used to classify hot numbers; 0 means all draws.
+    hot_window: Option<usize>,
+    /// Applied to `numbers`.
+    #[serde(default)]
+    validation: validation::ValidationConfig,
+}
+
+#[derive(Deserialize)]
+struct CoOccurrenceRequest {
+    /// Draws in chronological order, oldest first.
+    draws: Vec<ThaiLottoResult>,
+}
+
+#[derive(Deserialize)]
+struct CalendarRequest {
+    draws: Vec<ThaiLottoResult>,
+    field: PrizeField,
+}
+
+#[derive(Deserialize)]
+struct CheckTicketsRequest {
+    tickets: Vec<String>,
+    /// Draw to check against; every scraped draw when omitted.
+    draw_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+struct RoiRequest {
+    strategy: tickets::roi::Strategy,
+    /// Baht per ticket; the prize table's price when omitted.
+    ticket_price: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct WheelRequest {
+    targets: Vec<tickets::wheel::WheelTarget>,
+    ticket_price: Option<u64>,
+}
+
+/// Bumped on any breaking change to the `/analyze` response shape.
+const ANALYSIS_SCHEMA_VERSION: u32 = 3;
+
+#[derive(Serialize)]
+struct NumberCount {
+    number: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct PositionTopDigit {
+    position: usize,
+    digit: u8,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct PatternAnalysis {
+    /// Up to 10 numbers, most frequent first; ties go to the most recent, then the smaller.
+    most_frequent_numbers: Vec<NumberCount>,
+    top_digit_by_position: Vec<PositionTopDigit>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PredictionMethod {
+    BayesianDirichletMultinomial,
+}
+
+#[derive(Serialize)]
+struct PredictionOutput {
+    prediction: Option<String>,
+    /// Posterior probability of `prediction`, 0-1.
+    confidence: f64,
+    method: PredictionMethod,
+    alternative_predictions: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DetailedExplanation {
+    methodology: String,
+    statistical_evidence: String,
+    prediction_logic: String,
+    uncertainty_analysis: String,
 }
 
 #[derive(Serialize)]
 struct AnalysisResponse {
-    statistical_summary: HashMap<String, String>,
-    pattern_analysis: HashMap<String, serde_json::Value>,
-    prediction_output: HashMap<String, serde_json::Value>,
-    detailed_explanation: HashMap<String, String>,
-}
-
-fn run_comprehensive_analysis(numbers_str: &[String]) -> Result<AnalysisResponse, String> {
-    if numbers_str.len() < 10 { return Err(format!("ข้อมูลไม่เพียงพอ AI ต้องการชุดตัวเลขอย่างน้อย 10 ชุด แต่พบเพียง {} ชุด", numbers_str.len())); }
-
-    // --- Calculations on f64 (for math stats) ---
-    let numbers_f64: Vec<f64> = numbers_str.iter().filter_map(|s| s.parse::<f64>().ok()).collect();
-    if numbers_f64.len() < 5 { return Err("ไม่สามารถแปลงข้อมูลเป็นตัวเลขที่ถูกต้องเพื่อการวิเคราะห์ทางสถิติได้".to_string()); }
-
-    let data = Data::new(numbers_f64.clone());
-    let mean = data.mean().unwrap_or(0.0);
-    let median = data.median();
-    let std_dev = data.std_dev().unwrap_or(0.0);
-    let variance = data.variance().unwrap_or(0.0);
-    let min = data.min();
-    let max = data.max();
-    let skewness = Normal::new(mean, std_dev).unwrap().skewness().unwrap_or(0.0);
-    
-    // --- Calculations on original Strings (to preserve format like leading zeros) ---
-    let mut counts = HashMap::new();
-    for s in numbers_str {
-        *counts.entry(s.clone()).or_insert(0) += 1;
-    }
-    
-    // 1. Statistical Summary
-    let mode = counts.iter().max_by_key(|&(_, count)| count).map(|(val, _)| val.clone()).unwrap_or_else(|| "N/A".to_string());
-
-    let statistical_summary = HashMap::from([
-        ("Dataset Size".to_string(), numbers_str.len().to_string()),
-        ("Mean".to_string(), format!("{:.2}", mean)),
-        ("Median".to_string(), format!("{:.2}", median)),
-        ("Mode (ฐานนิยม)".to_string(), mode.clone()),
-        ("Std. Dev.".to_string(), format!("{:.2}", std_dev)),
-        ("Variance".to_string(), format!("{:.2}", variance)),
-        ("Range".to_string(), format!("{:.2} - {:.2}", min, max)),
-        ("Distribution Skewness".to_string(), format!("{:.4}", skewness)),
-    ]);
+    schema_version: u32,
+    /// Every number read as an integer on its own digit-width scale.
+    numeric_summary: stats::descriptive::NumericSummary,
+    /// Every number treated as a label, so "05" and "5" differ.
+    categorical_summary: stats::descriptive::CategoricalSummary,
+    pattern_analysis: PatternAnalysis,
+    prediction_output: PredictionOutput,
+    detailed_explanation: DetailedExplanation,
+    bayesian_estimate: stats::bayes::BayesReport,
+    validation: validation::ValidationReport,
+}
+
+fn run_comprehensive_analysis(input: validation::ValidatedNumbers, locale: Locale) -> Result<AnalysisResponse, Message> {
+    let numbers_str = &input.numbers[..];
+    let series: Vec<&str> = numbers_str.iter().map(String::as_str).collect();
+
+    // 1. Statistical Summary: numeric on the series' own width, categorical on the raw strings
+    let numeric_summary = stats::descriptive::describe_numeric(&series).ok_or(Message::DescriptiveStatsFailed)?;
+    let categorical_summary = stats::descriptive::describe_categorical(&series).ok_or(Message::DescriptiveStatsFailed)?;
+    let std_dev = numeric_summary.stats.std_dev.unwrap_or(0.0);
 
     // 2. Pattern Recognition
-    let most_frequent: Vec<String> = counts.iter().take(10).map(|(k, v)| format!("{} ({} times)", k, v)).collect();
-    
-    let mut digit_pos_freq: HashMap<usize, HashMap<char, usize>> = HashMap::new();
-    for num_str in numbers_str {
-        for (i, c) in num_str.chars().enumerate() {
-            *digit_pos_freq.entry(i).or_default().entry(c).or_default() += 1;
-        }
-    }
-    let digit_analysis_str: Vec<String> = digit_pos_freq.iter()
-        .map(|(pos, freqs)| {
-            let top_digit = freqs.iter().max_by_key(|&(_, count)| count).map(|(d, c)| format!("'{}' ({} times)", d, c)).unwrap_or_default();
-            format!("Position {}: Most frequent is {}", pos + 1, top_digit)
-        }).collect();
-
-    let pattern_analysis = HashMap::from([
-        ("Most Frequent Numbers".to_string(), serde_json::json!(most_frequent)),
-        ("Digit & Position Analysis".to_string(), serde_json::json!(digit_analysis_str)),
-    ]);
-    
-    // 3. Prediction Output
-    let main_prediction = mode;
-    let alternatives: Vec<String> = counts.iter().filter(|(k, _)| **k != main_prediction).take(4).map(|(k, _)| k.clone()).collect();
-    let confidence = (60.0 + (numbers_str.len() as f64 / 100.0 * 20.0)).min(95.0);
-
-    let prediction_output = HashMap::from([
-        ("PREDICTION".to_string(), serde_json::json!(main_prediction.clone())),
-        ("CONFIDENCE".to_string(), serde_json::json!(format!("{:.2}%", confidence))),
-        ("METHOD".to_string(), serde_json::json!("Weighted Statistical & Frequency Model")),
-        ("ALTERNATIVE_PREDICTIONS".to_string(), serde_json::json!(alternatives)),
-    ]);
+    let most_frequent: Vec<NumberCount> = stats::rank_by_frequency(&series).into_iter().take(10).map(|r| NumberCount { number: r.value.to_string(), count: r.count }).collect();
+
+    let digit_matrix = stats::positional::digit_position_matrix(numbers_str).map_err(|_| Message::InvalidDigitSeries)?;
+    let top_digit_by_position = digit_matrix.top_digits().iter()
+        .map(|(pos, cell)| PositionTopDigit { position: *pos, digit: cell.digit, count: cell.count })
+        .collect();
+
+    let pattern_analysis = PatternAnalysis { most_frequent_numbers: most_frequent, top_digit_by_position };
+
+    // 3. Prediction Output (Bayesian Dirichlet-multinomial posterior)
+    let bayes = stats::bayes::bayes_analysis(numbers_str).map_err(|_| Message::InvalidDigitSeries)?;
+    let main_prediction = bayes.predictions.first().map(|p| p.number.clone());
+    let prediction_probability = bayes.predictions.first().map_or(0.0, |p| p.probability);
+    let alternatives: Vec<String> = bayes.predictions.iter().skip(1).take(4).map(|p| p.number.clone()).collect();
+    let posterior = match (&bayes.endings, digit_matrix.digit_width) {
+        (Some(endings), 2) => endings,
+        _ => bayes.positions.iter().map(|p| &p.posterior).max_by(|a, b| a.max_shift_from_uniform.total_cmp(&b.max_shift_from_uniform)).unwrap(),
+    };
 
     // 4. Detailed Explanation
-    let explanation = HashMap::from([
-        ("Methodology".to_string(), "ใช้โมเดลผสมระหว่างการวิเคราะห์ความถี่ (Frequency Analysis) และค่าสถิติสำคัญ (Statistical Significance) โดยให้ความสำคัญกับตัวเลขที่ปรากฏบ่อยที่สุด (Mode) ในรูปแบบดั้งเดิมเป็นหลัก".to_string()),
-        ("Statistical Evidence".to_string(), format!("ตัวเลข '{}' เป็นฐานนิยม (Mode) ซึ่งปรากฏบ่อยที่สุดในชุดข้อมูล การกระจายตัวของข้อมูลมีค่าเบี่ยงเบนมาตรฐานที่ {:.2} ซึ่งบ่งชี้ถึงความผันผวนของข้อมูล", main_prediction, std_dev)),
-        ("Prediction Logic".to_string(), "การทำนายหลักมาจากค่าฐานนิยม (Mode) ซึ่งเป็นตัวบ่งชี้ทางสถิติที่แข็งแกร่งที่สุดในข้อมูลชุดนี้สำหรับตัวเลขที่จะออกซ้ำ ตัวเลือกสำรองมาจากตัวเลขที่มีความถี่รองลงมา".to_string()),
-        ("Uncertainty Analysis".to_string(), "ระดับความมั่นใจประเมินจากขนาดของชุดข้อมูลและความเด่นชัดของฐานนิยม ความผันผวนของข้อมูลยังคงเป็นปัจจัยสำคัญที่สร้างความไม่แน่นอน".to_string()),
-    ]);
+    let explanation = DetailedExplanation {
+        methodology: Message::Methodology { prior_alpha: bayes.prior_alpha }.text(locale),
+        statistical_evidence: Message::StatisticalEvidence { prediction: main_prediction.clone(), probability: prediction_probability, std_dev }.text(locale),
+        prediction_logic: Message::PredictionLogic.text(locale),
+        uncertainty_analysis: Message::UncertaintyAnalysis { sample_size: bayes.sample_size, uniform_probability: posterior.uniform_probability, max_shift: posterior.max_shift_from_uniform, kl_divergence: posterior.kl_divergence_from_uniform, credible_mass: bayes.credible_mass }.text(locale),
+    };
+
+    let prediction_output = PredictionOutput {
+        prediction: main_prediction,
+        confidence: prediction_probability,
+        method: PredictionMethod::BayesianDirichletMultinomial,
+        alternative_predictions: alternatives,
+    };
 
     Ok(AnalysisResponse {
-        statistical_summary,
+        schema_version: ANALYSIS_SCHEMA_VERSION,
+        numeric_summary,
+        categorical_summary,
         pattern_analysis,
         prediction_output,
         detailed_explanation: explanation,
+        bayesian_estimate: bayes,
+        validation: input.report,
     })
 }
 
@@ -219,29 +558,552 @@ struct StartScrapeRequest {
     lotto_type: String,
 }
 
-async fn start_scrape(req: web::Json<StartScrapeRequest>) -> impl Responder {
+async fn start_scrape(http: HttpRequest, req: web::Json<StartScrapeRequest>) -> impl Responder {
+    let locale = Locale::from_request(&http);
     let mut status = TASK_STATUS.lock().unwrap();
-    if status.is_running { return HttpResponse::Conflict().json(serde_json::json!({"error": "A scraper is already running."})); }
-    if req.lotto_type != "thai" { return HttpResponse::BadRequest().json(serde_json::json!({"error": "Invalid lottery type."})); }
+    if status.is_running { return HttpResponse::Conflict().json(serde_json::json!({"error": Message::ScraperAlreadyRunning.text(locale)})); }
+    if req.lotto_type != "thai" { return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::InvalidLotteryType.text(locale)})); }
     status.is_running = true;
     status.lotto_type = Some(req.lotto_type.clone());
-    status.progress = vec!["🚀 Starting scraper for Thai Lottery...".to_string()];
-    status.results.clear();
+    status.progress = vec![Message::ScrapeStarting];
     tokio::spawn(run_scraper());
-    HttpResponse::Accepted().json(serde_json::json!({"message": "Scraping process started!"}))
+    HttpResponse::Accepted().json(serde_json::json!({"message": Message::ScrapeStarted.text(locale)}))
+}
+
+async fn get_status(http: HttpRequest) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    let status = TASK_STATUS.lock().unwrap();
+    HttpResponse::Ok().json(StatusResponse {
+        is_running: status.is_running,
+        lotto_type: &status.lotto_type,
+        progress: status.progress.iter().map(|m| m.text(locale)).collect(),
+        results: &status.results,
+    })
+}
+
+async fn draws_handler(http: HttpRequest, query: web::Query<DrawsQuery>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    if query.lotto_type != "thai" { return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::InvalidLotteryType.text(locale)})); }
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(DEFAULT_DRAWS_PER_PAGE).clamp(1, MAX_DRAWS_PER_PAGE);
+    let status = TASK_STATUS.lock().unwrap();
+    let draws = query.select(&status.results);
+    let total = draws.len();
+    let draws = paginate(draws, page, per_page);
+    HttpResponse::Ok().json(DrawPage { total, page, per_page, draws })
 }
 
-async fn get_status() -> impl Responder {
-    HttpResponse::Ok().json(&*TASK_STATUS.lock().unwrap())
+/// Items on the 1-based `page`; empty past the last page, however large `page` is.
+fn paginate<T>(items: Vec<T>, page: usize, per_page: usize) -> Vec<T> {
+    items.into_iter().skip((page - 1).saturating_mul(per_page)).take(per_page).collect()
 }
 
-async fn analyze_handler(req: web::Json<AnalyzeRequest>) -> impl Responder {
-    match run_comprehensive_analysis(&req.numbers) {
+async fn draw_by_date_handler(http: HttpRequest, date: web::Path<NaiveDate>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    let date = date.into_inner();
+    let status = TASK_STATUS.lock().unwrap();
+    match status.results.iter().find(|d| d.draw_date == Some(date)) {
+        Some(draw) => HttpResponse::Ok().json(draw),
+        None => HttpResponse::NotFound().json(serde_json::json!({"error": Message::DrawNotFound { date }.text(locale)})),
+    }
+}
+
+async fn analyze_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>) -> impl Responder {
+    match analyze_numbers(&req.numbers, &req.validation, Locale::from_request(&http)) {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(error) => error,
+    }
+}
+
+async fn analyze_history_handler(http: HttpRequest, req: web::Json<AnalyzeHistoryRequest>) -> impl Responder {
+    match analyze_history(&req, Locale::from_request(&http)) {
         Ok(response) => HttpResponse::Ok().json(response),
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e })),
+        Err(error) => error,
+    }
+}
+
+fn analyze_history(req: &AnalyzeHistoryRequest, locale: Locale) -> Result<AnalysisResponse, HttpResponse> {
+    if req.selector.lotto_type != "thai" { return Err(HttpResponse::BadRequest().json(serde_json::json!({"error": Message::InvalidLotteryType.text(locale)}))); }
+    let numbers = {
+        let status = TASK_STATUS.lock().unwrap();
+        if status.results.is_empty() { return Err(HttpResponse::BadRequest().json(serde_json::json!({"error": Message::NoStoredDraws.text(locale)}))); }
+        req.selector.select(&status.results)
+    };
+    if numbers.is_empty() { return Err(HttpResponse::NotFound().json(serde_json::json!({"error": Message::NoDrawsSelected.text(locale)}))); }
+    analyze_numbers(&numbers, &req.validation, locale)
+}
+
+/// Validates `numbers`; the error is the response to send back, carrying the report.
+fn validate_request(numbers: &[String], config: &validation::ValidationConfig, locale: Locale) -> Result<validation::ValidatedNumbers, HttpResponse> {
+    validation::validate_numbers(numbers, config).map_err(|error| match error {
+        validation::ValidationError::Config(message) => HttpResponse::BadRequest().json(serde_json::json!({ "error": message.text(locale) })),
+        validation::ValidationError::Entries(report) => {
+            let rejected = report.issues.iter().filter(|i| i.action == validation::IssueAction::Rejected).count();
+            let message = if rejected > 0 { Message::InvalidEntriesRejected { count: rejected } } else { Message::InsufficientAnalysisData { required: report.min_size, found: report.accepted } };
+            HttpResponse::BadRequest().json(serde_json::json!({ "error": message.text(locale), "validation": report }))
+        }
+    })
+}
+
+/// Validates `numbers` and runs the full analysis; the error is the response to send back.
+fn analyze_numbers(numbers: &[String], config: &validation::ValidationConfig, locale: Locale) -> Result<AnalysisResponse, HttpResponse> {
+    let input = validate_request(numbers, config, locale)?;
+    run_comprehensive_analysis(input, locale).map_err(|e| HttpResponse::BadRequest().json(serde_json::json!({ "error": e.text(locale) })))
+}
+
+/// A series analysis together with the validation report of its input.
+#[derive(Serialize)]
+struct ValidatedReport<T> {
+    #[serde(flatten)]
+    report: T,
+    validation: validation::ValidationReport,
+}
+
+/// Validates `numbers` and runs `analysis` on the cleaned series.
+fn series_response<T: Serialize>(numbers: &[String], config: &validation::ValidationConfig, locale: Locale, analysis: impl FnOnce(&[String]) -> Result<T, Message>) -> HttpResponse {
+    let input = match validate_request(numbers, config, locale) {
+        Ok(input) => input,
+        Err(error) => return error,
+    };
+    series_result(analysis(&input.numbers), input.report, locale)
+}
+
+fn series_result<T: Serialize>(result: Result<T, Message>, validation: validation::ValidationReport, locale: Locale) -> HttpResponse {
+    match result {
+        Ok(report) => HttpResponse::Ok().json(ValidatedReport { report, validation }),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.text(locale), "validation": validation })),
+    }
+}
+
+/// Responds with `result`, rendering an error in `locale`; for requests with no validation report.
+fn result_response<T: Serialize>(result: Result<T, Message>, locale: Locale) -> HttpResponse {
+    match result {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({ "error": e.text(locale) })),
+    }
+}
+
+// --- Exports ---
+
+fn export_response(format: export::ExportFormat, name: &str, body: Result<Vec<u8>, Message>, locale: Locale) -> HttpResponse {
+    match body {
+        Ok(body) => HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(("Content-Disposition", format!("attachment; filename=\"{}.{}\"", name, format.extension())))
+            .body(body),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({ "error": e.text(locale) })),
     }
 }
 
+fn draw_date_cell(draw: &ThaiLottoResult) -> export::Cell {
+    draw.draw_date.map_or(export::Cell::Empty, export::Cell::Date)
+}
+
+/// CSV and JSON Lines get one row per draw; Excel gets one sheet per prize tier with
+/// one row per winning number.
+fn export_draws(format: export::ExportFormat, draws: &[&ThaiLottoResult]) -> Result<Vec<u8>, String> {
+    use export::{Cell, ExportFormat, Table};
+    match format {
+        ExportFormat::Jsonl => export::to_jsonl(draws),
+        ExportFormat::Csv | ExportFormat::ExcelCsv => export::to_csv(&Table {
+            name: "Draws".to_string(),
+            headers: std::iter::once("Draw Date").chain(PRIZE_TIER_COLUMNS).map(str::to_string).collect(),
+            rows: draws.iter().map(|d| std::iter::once(draw_date_cell(d)).chain(d.prize_tiers().into_iter().map(|numbers| Cell::Text(numbers.join(" ")))).collect()).collect(),
+        }, format == ExportFormat::ExcelCsv),
+        ExportFormat::Xlsx => {
+            let sheets: Vec<Table> = PRIZE_TIER_COLUMNS.iter().enumerate().map(|(tier, name)| Table {
+                name: name.to_string(),
+                headers: vec!["Draw Date".to_string(), "Number".to_string()],
+                rows: draws.iter().flat_map(|d| d.prize_tiers()[tier].clone().into_iter().map(move |n| vec![draw_date_cell(d), Cell::Text(n)])).collect(),
+            }).collect();
+            export::to_xlsx(&sheets)
+        }
+        ExportFormat::Parquet => export::to_parquet(&draws_record_batch(draws)?),
+        ExportFormat::Arrow => export::to_arrow_ipc(&draws_record_batch(draws)?),
+    }
+}
+
+/// One row per draw, columns named by [`PRIZE_TIER_FIELDS`]: `draw_date` as a date, the
+/// first prize and last-2 number as text plus one digit column per position
+/// (`first_prize_d1`, ...), and the multi-number tiers as lists of text.
+fn draws_record_batch(draws: &[&ThaiLottoResult]) -> Result<arrow::record_batch::RecordBatch, String> {
+    use arrow::array::{Array, ArrayRef, Date32Array, ListBuilder, StringArray, StringBuilder, UInt8Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    let epoch = chrono::DateTime::UNIX_EPOCH.date_naive();
+    let mut fields = vec![Field::new("draw_date", DataType::Date32, true)];
+    let mut columns: Vec<ArrayRef> = vec![Arc::new(draws.iter().map(|d| d.draw_date.map(|date| (date - epoch).num_days() as i32)).collect::<Date32Array>())];
+
+    let tiers: Vec<[Vec<String>; 8]> = draws.iter().map(|d| d.prize_tiers()).collect();
+    for (tier, name) in PRIZE_TIER_FIELDS.into_iter().enumerate() {
+        if name == "first_prize" || name == "last_2_digits" {
+            let width = PRIZE_TIER_WIDTHS[tier];
+            let numbers: Vec<&str> = tiers.iter().map(|t| t[tier].first().map_or("", String::as_str)).collect();
+            fields.push(Field::new(name, DataType::Utf8, false));
+            columns.push(Arc::new(StringArray::from_iter_values(&numbers)));
+            for pos in 0..width {
+                // Null unless the number has exactly `width` digits, so positions never shift.
+                let digits = numbers.iter().map(|n| Some(n).filter(|n| n.len() == width && n.bytes().all(|b| b.is_ascii_digit())).map(|n| n.as_bytes()[pos] - b'0'));
+                fields.push(Field::new(format!("{}_d{}", name, pos + 1), DataType::UInt8, true));
+                columns.push(Arc::new(digits.collect::<UInt8Array>()));
+            }
+        } else {
+            let mut builder = ListBuilder::new(StringBuilder::new());
+            for t in &tiers {
+                builder.append_value(t[tier].iter().map(Some));
+            }
+            let list = builder.finish();
+            fields.push(Field::new(name, list.data_type().clone(), false));
+            columns.push(Arc::new(list));
+        }
+    }
+    arrow::record_batch::RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(|e| e.to_string())
+}
+
+/// CSV lists every field as a path/value row and Excel splits those rows into one sheet
+/// per top-level section; JSON Lines is the response itself on one line.
+fn export_analysis(format: export::ExportFormat, response: &AnalysisResponse) -> Result<Vec<u8>, Message> {
+    use export::{ExportFormat, Table};
+    let headers = vec!["Field".to_string(), "Value".to_string()];
+    let fields = || -> Result<Vec<(String, export::Cell)>, Message> { Ok(export::flatten_json(&serde_json::to_value(response).map_err(|e| Message::ExportFailed { error: e.to_string() })?)) };
+    let body = match format {
+        ExportFormat::Jsonl => export::to_jsonl([response]),
+        ExportFormat::Csv | ExportFormat::ExcelCsv => export::to_csv(&Table { name: "Analysis".to_string(), headers, rows: fields()?.into_iter().map(|(path, value)| vec![export::Cell::Text(path), value]).collect() }, format == ExportFormat::ExcelCsv),
+        ExportFormat::Xlsx => {
+            let mut sheets: Vec<Table> = Vec::new();
+            for (path, value) in fields()? {
+                // Scalars at the top level, such as the schema version, share an overview sheet.
+                let (section, rest) = match path.find(['.', '[']) {
+                    Some(i) => (&path[..i], path[i..].trim_start_matches('.')),
+                    None => ("overview", path.as_str()),
+                };
+                let row = vec![export::Cell::Text(rest.to_string()), value];
+                match sheets.iter_mut().find(|s| s.name == section) {
+                    Some(sheet) => sheet.rows.push(row),
+                    None => sheets.push(Table { name: section.to_string(), headers: headers.clone(), rows: vec![row] }),
+                }
+            }
+            export::to_xlsx(&sheets)
+        }
+        ExportFormat::Parquet | ExportFormat::Arrow => return Err(Message::ColumnarExportUnsupported),
+    };
+    body.map_err(|error| Message::ExportFailed { error })
+}
+
+async fn export_draws_handler(http: HttpRequest, query: web::Query<DrawsQuery>, export: web::Query<ExportQuery>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    if query.lotto_type != "thai" { return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::InvalidLotteryType.text(locale)})); }
+    let body = {
+        let status = TASK_STATUS.lock().unwrap();
+        export_draws(export.format, &query.select(&status.results)).map_err(|error| Message::ExportFailed { error })
+    };
+    export_response(export.format, "draws", body, locale)
+}
+
+async fn export_analysis_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>, export: web::Query<ExportQuery>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    if export.format.is_columnar() { return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::ColumnarExportUnsupported.text(locale)})); }
+    match analyze_numbers(&req.numbers, &req.validation, locale) {
+        Ok(response) => export_response(export.format, "analysis", export_analysis(export.format, &response), locale),
+        Err(error) => error,
+    }
+}
+
+async fn export_analysis_history_handler(http: HttpRequest, req: web::Json<AnalyzeHistoryRequest>, export: web::Query<ExportQuery>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    if export.format.is_columnar() { return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::ColumnarExportUnsupported.text(locale)})); }
+    match analyze_history(&req, locale) {
+        Ok(response) => export_response(export.format, "analysis", export_analysis(export.format, &response), locale),
+        Err(error) => error,
+    }
+}
+
+// --- Imports ---
+
+/// Digit width of each tier, in [`PRIZE_TIER_COLUMNS`] order.
+const PRIZE_TIER_WIDTHS: [usize; 8] = [6, 3, 3, 2, 6, 6, 6, 6];
+const MAX_IMPORT_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum ImportFormat {
+    /// The columns of the CSV export; numbers sharing a cell are space- or comma-separated.
+    #[default]
+    Csv,
+    /// An array of draws shaped like the `/status` results.
+    Json,
+    Jsonl,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ConflictPolicy {
+    /// Keep the stored draw.
+    #[default]
+    Skip,
+    Replace,
+}
+
+#[derive(Deserialize)]
+struct ImportQuery {
+    #[serde(default)]
+    format: ImportFormat,
+    /// Report what would change without touching the store.
+    #[serde(default)]
+    dry_run: bool,
+    #[serde(default)]
+    on_conflict: ConflictPolicy,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ImportRejectReason {
+    Malformed { error: String },
+    MissingDrawDate,
+    MissingField { field: &'static str },
+    InvalidNumbers { field: &'static str, issues: Vec<validation::ValidationIssue> },
+    /// An earlier record of the upload has the same date.
+    DuplicateDate,
+}
+
+#[derive(Serialize)]
+struct ImportReject {
+    /// 1-based position in the upload: CSV data row, array element or non-blank line.
+    record: usize,
+    #[serde(flatten)]
+    reason: ImportRejectReason,
+}
+
+#[derive(Serialize)]
+struct ImportConflict {
+    draw_date: NaiveDate,
+    /// Columns whose numbers differ from the stored draw.
+    fields: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ImportReport {
+    dry_run: bool,
+    on_conflict: ConflictPolicy,
+    submitted: usize,
+    inserted: Vec<NaiveDate>,
+    /// Already stored with the same numbers.
+    unchanged: usize,
+    conflicts: Vec<ImportConflict>,
+    rejected: Vec<ImportReject>,
+}
+
+/// Splits an upload into records. A record that cannot be read becomes a reject; only an
+/// upload that cannot be read at all is an error.
+fn parse_import(format: ImportFormat, body: &[u8]) -> Result<Vec<Result<ThaiLottoResult, ImportRejectReason>>, String> {
+    let malformed = |e: &dyn std::fmt::Display| ImportRejectReason::Malformed { error: e.to_string() };
+    match format {
+        ImportFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_slice(body).map_err(|e| e.to_string())?;
+            Ok(values.into_iter().map(|v| serde_json::from_value(v).map_err(|e| malformed(&e))).collect())
+        }
+        ImportFormat::Jsonl => {
+            let text = std::str::from_utf8(body).map_err(|e| e.to_string())?;
+            Ok(text.lines().filter(|l| !l.trim().is_empty()).map(|l| serde_json::from_str(l).map_err(|e| malformed(&e))).collect())
+        }
+        ImportFormat::Csv => {
+            // The reader skips the BOM that the CSV export writes.
+            let mut reader = csv::Reader::from_reader(body);
+            let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+            Ok(reader.records().map(|record| draw_from_csv(&headers, &record.map_err(|e| malformed(&e))?)).collect())
+        }
+    }
+}
+
+fn draw_from_csv(headers: &csv::StringRecord, record: &csv::StringRecord) -> Result<ThaiLottoResult, ImportRejectReason> {
+    let column = |name: &str| headers.iter().position(|h| h.trim() == name).and_then(|i| record.get(i)).map_or("", str::trim);
+    let numbers = |name: &str| column(name).split(|c: char| c == ',' || c.is_whitespace()).filter(|n| !n.is_empty()).map(str::to_string).collect::<Vec<String>>();
+    let draw_date = match column("Draw Date") {
+        "" => None,
+        date => Some(parse_draw_date(date).ok_or_else(|| ImportRejectReason::Malformed { error: format!("invalid draw date \"{}\"", date) })?),
+    };
+    let [first_prize, front_3_digits, last_3_digits, last_2_digits, second_prizes, third_prizes, fourth_prizes, fifth_prizes] = PRIZE_TIER_COLUMNS.map(numbers);
+    Ok(ThaiLottoResult { draw_date, first_prize: first_prize.join(" "), front_3_digits, last_3_digits, last_2_digits: last_2_digits.join(" "), second_prizes, third_prizes, fourth_prizes, fifth_prizes })
+}
+
+/// Holds an imported draw to what the scraper keeps (a first prize and a last-2 number)
+/// and to the `/analyze` digit checks at each tier's width. Imports also need a date,
+/// since that is what they merge on.
+fn validate_import(mut draw: ThaiLottoResult) -> Result<(NaiveDate, ThaiLottoResult), ImportRejectReason> {
+    let date = draw.draw_date.and_then(to_gregorian).ok_or(ImportRejectReason::MissingDrawDate)?;
+    draw.draw_date = Some(date);
+    draw.first_prize = draw.first_prize.trim().to_string();
+    draw.last_2_digits = draw.last_2_digits.trim().to_string();
+    for tier in [&mut draw.front_3_digits, &mut draw.last_3_digits, &mut draw.second_prizes, &mut draw.third_prizes, &mut draw.fourth_prizes, &mut draw.fifth_prizes] {
+        tier.iter_mut().for_each(|n| *n = n.trim().to_string());
+    }
+    for (tier, numbers) in draw.prize_tiers().iter().enumerate() {
+        let field = PRIZE_TIER_COLUMNS[tier];
+        if numbers.iter().all(String::is_empty) {
+            if field == "First Prize" || field == "Last 2 Digits" { return Err(ImportRejectReason::MissingField { field }); }
+            continue;
+        }
+        let config = validation::ValidationConfig { policy: validation::ValidationPolicy::Reject, min_size: 1, digit_width: Some(PRIZE_TIER_WIDTHS[tier]) };
+        if let Err(validation::ValidationError::Entries(report)) = validation::validate_numbers(numbers, &config) { return Err(ImportRejectReason::InvalidNumbers { field, issues: report.issues }); }
+    }
+    Ok((date, draw))
+}
+
+/// Validates parsed records, rejecting any whose date an earlier record in the upload
+/// already has.
+fn accept_import(records: Vec<Result<ThaiLottoResult, ImportRejectReason>>, report: &mut ImportReport) -> Vec<(NaiveDate, ThaiLottoResult)> {
+    let mut accepted: Vec<(NaiveDate, ThaiLottoResult)> = Vec::new();
+    for (i, record) in records.into_iter().enumerate() {
+        let reason = match record.and_then(validate_import) {
+            Ok((date, _)) if accepted.iter().any(|(d, _)| *d == date) => ImportRejectReason::DuplicateDate,
+            Ok(draw) => { accepted.push(draw); continue; }
+            Err(reason) => reason,
+        };
+        report.rejected.push(ImportReject { record: i + 1, reason });
+    }
+    accepted
+}
+
+/// Merges accepted draws into `results` by date, newest first. The report is filled in
+/// either way; `results` only changes when `apply` is set.
+fn merge_import(results: &mut Vec<ThaiLottoResult>, accepted: Vec<(NaiveDate, ThaiLottoResult)>, apply: bool, report: &mut ImportReport) {
+    for (date, draw) in accepted {
+        match results.iter().position(|d| d.draw_date == Some(date)) {
+            None => {
+                report.inserted.push(date);
+                if apply { results.push(draw); }
+            }
+            Some(i) if results[i] == draw => report.unchanged += 1,
+            Some(i) => {
+                let fields = draw.prize_tiers().iter().zip(results[i].prize_tiers()).zip(PRIZE_TIER_COLUMNS).filter(|((new, old), _)| *new != old).map(|(_, field)| field).collect();
+                report.conflicts.push(ImportConflict { draw_date: date, fields });
+                if apply && report.on_conflict == ConflictPolicy::Replace { results[i] = draw; }
+            }
+        }
+    }
+    if apply && !report.inserted.is_empty() {
+        results.sort_by_key(|d| std::cmp::Reverse(d.draw_date));
+    }
+}
+
+async fn import_draws_handler(http: HttpRequest, query: web::Query<ImportQuery>, body: web::Bytes) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    let records = match parse_import(query.format, &body) {
+        Ok(records) => records,
+        Err(error) => return HttpResponse::BadRequest().json(serde_json::json!({"error": Message::ImportUnreadable { error }.text(locale)})),
+    };
+    let mut report = ImportReport { dry_run: query.dry_run, on_conflict: query.on_conflict, submitted: records.len(), inserted: Vec::new(), unchanged: 0, conflicts: Vec::new(), rejected: Vec::new() };
+    let accepted = accept_import(records, &mut report);
+
+    let apply = !query.dry_run;
+    let mut status = TASK_STATUS.lock().unwrap();
+    if apply && status.is_running { return HttpResponse::Conflict().json(serde_json::json!({"error": Message::ScraperAlreadyRunning.text(locale)})); }
+    merge_import(&mut status.results, accepted, apply, &mut report);
+    HttpResponse::Ok().json(report)
+}
+
+async fn randomness_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>) -> impl Responder {
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), stats::randomness::run_randomness_suite)
+}
+
+async fn digit_matrix_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>) -> impl Responder {
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), stats::positional::digit_position_matrix)
+}
+
+async fn gaps_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>) -> impl Responder {
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), stats::gaps::gap_analysis)
+}
+
+async fn hot_cold_handler(http: HttpRequest, req: web::Json<HotColdRequest>) -> impl Responder {
+    let windows: Vec<Option<usize>> = if req.windows.is_empty() { stats::hot_cold::DEFAULT_WINDOWS.to_vec() } else { req.windows.iter().map(|&w| (w > 0).then_some(w)).collect() };
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), |series| stats::hot_cold::hot_cold_analysis(series, &windows))
+}
+
+async fn cooccurrence_handler(http: HttpRequest, req: web::Json<CoOccurrenceRequest>) -> impl Responder {
+    let draws: Vec<(&str, &str)> = req.draws.iter().map(|d| (d.first_prize.as_str(), d.last_2_digits.as_str())).collect();
+    result_response(stats::cooccurrence::cooccurrence_analysis(&draws), Locale::from_request(&http))
+}
+
+async fn permutations_handler(http: HttpRequest, req: web::Json<PermutationRequest>) -> impl Responder {
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), |series| stats::permutations::permutation_ranking(series, req.prediction.as_deref()))
+}
+
+async fn markov_handler(http: HttpRequest, req: web::Json<AnalyzeRequest>) -> impl Responder {
+    series_response(&req.numbers, &req.validation, Locale::from_request(&http), stats::markov::markov_analysis)
+}
+
+async fn monte_carlo_handler(http: HttpRequest, req: web::Json<MonteCarloRequest>) -> impl Responder {
+    let statistics = if req.statistics.is_empty() { stats::monte_carlo::ALL_STATISTICS.to_vec() } else { req.statistics.clone() };
+    let simulations = req.simulations.unwrap_or(stats::monte_carlo::DEFAULT_SIMULATIONS);
+    let locale = Locale::from_request(&http);
+    let input = match validate_request(&req.numbers, &req.validation, locale) {
+        Ok(input) => input,
+        Err(error) => return error,
+    };
+    let seed = req.seed;
+    match web::block(move || (stats::monte_carlo::monte_carlo_analysis(&input.numbers, &statistics, simulations, seed), input.report)).await {
+        Ok((result, validation)) => series_result(result, validation, locale),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({ "error": Message::AnalysisFailed { error: e.to_string() }.text(locale) })),
+    }
+}
+
+async fn calendar_handler(http: HttpRequest, req: web::Json<CalendarRequest>) -> impl Responder {
+    let draws: Vec<(NaiveDate, Vec<String>)> = req.draws.iter().filter_map(|d| Some((d.draw_date?, d.numbers(req.field)))).collect();
+    result_response(stats::calendar::calendar_analysis(&draws), Locale::from_request(&http))
+}
+
+async fn generate_handler(http: HttpRequest, req: web::Json<GenerateRequest>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    let mut req = req.into_inner();
+    if req.must_end_in_hot {
+        let window = req.hot_window.unwrap_or(24);
+        let input = match validate_request(&req.numbers, &req.validation, locale) {
+            Ok(input) => input,
+            Err(error) => return error,
+        };
+        let report = match stats::hot_cold::hot_cold_analysis(&input.numbers, &[(window > 0).then_some(window)]) {
+            Ok(report) => report,
+            Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({ "error": e.text(locale), "validation": input.report })),
+        };
+        let hot: Vec<String> = report.windows[0].endings.iter().filter(|e| e.temperature == stats::hot_cold::Temperature::Hot).map(|e| e.value.clone()).collect();
+        if hot.is_empty() { return HttpResponse::BadRequest().json(serde_json::json!({ "error": Message::NoHotEndings.text(locale) })); }
+        if req.constraints.allowed_endings.is_empty() {
+            req.constraints.allowed_endings = hot;
+        } else {
+            req.constraints.allowed_endings.retain(|e| hot.iter().any(|h| h == e.trim()));
+            if req.constraints.allowed_endings.is_empty() { return HttpResponse::BadRequest().json(serde_json::json!({ "error": Message::NoAllowedEndings.text(locale) })); }
+        }
+    }
+    let width = req.digits.unwrap_or(6);
+    let count = req.count.unwrap_or(tickets::quick_pick::DEFAULT_TICKET_COUNT);
+    result_response(tickets::quick_pick::generate_quick_picks(width, count, &req.constraints, req.seed), locale)
+}
+
+async fn check_tickets_handler(http: HttpRequest, req: web::Json<CheckTicketsRequest>) -> impl Responder {
+    let locale = Locale::from_request(&http);
+    let status = TASK_STATUS.lock().unwrap();
+    if status.results.is_empty() { return HttpResponse::BadRequest().json(serde_json::json!({ "error": Message::NoStoredDraws.text(locale) })); }
+    let draws: Vec<tickets::checker::DrawNumbers> = status.results.iter().filter(|r| req.draw_date.is_none() || r.draw_date == req.draw_date).map(ThaiLottoResult::draw_numbers).collect();
+    drop(status);
+    if let (Some(date), true) = (req.draw_date, draws.is_empty()) { return HttpResponse::NotFound().json(serde_json::json!({ "error": Message::DrawNotFound { date }.text(locale) })); }
+    result_response(tickets::checker::check_tickets(&req.tickets, &draws), locale)
+}
+
+async fn roi_handler(http: HttpRequest, req: web::Json<RoiRequest>) -> impl Responder {
+    // Scraped results are newest first; the replay runs oldest first.
+    let draws: Vec<tickets::checker::DrawNumbers> = TASK_STATUS.lock().unwrap().results.iter().rev().map(ThaiLottoResult::draw_numbers).collect();
+    result_response(tickets::roi::roi_analysis(&req.strategy, req.ticket_price, &draws), Locale::from_request(&http))
+}
+
+async fn wheel_handler(http: HttpRequest, req: web::Json<WheelRequest>) -> impl Responder {
+    result_response(tickets::wheel::generate_wheel(&req.targets, req.ticket_price), Locale::from_request(&http))
+}
+
+async fn prize_tables_handler() -> impl Responder {
+    HttpResponse::Ok().json(&tickets::prizes::PRIZE_TABLES)
+}
+
 async fn index() -> impl Responder {
     match std::fs::read_to_string("templates/index.html") {
         Ok(content) => HttpResponse::Ok().content_type("text/html; charset=utf-8").body(content),
@@ -261,10 +1123,259 @@ async fn main() -> std::io::Result<()> {
             .route("/", web::get().to(index))
             .route("/start-scrape", web::post().to(start_scrape))
             .route("/status", web::get().to(get_status))
+            .route("/draws", web::get().to(draws_handler))
+            .route("/draws/export", web::get().to(export_draws_handler))
+            .service(web::resource("/draws/import").app_data(web::PayloadConfig::new(MAX_IMPORT_BYTES)).route(web::post().to(import_draws_handler)))
+            .route("/draws/{date}", web::get().to(draw_by_date_handler))
             .route("/analyze", web::post().to(analyze_handler))
+            .route("/analyze-history", web::post().to(analyze_history_handler))
+            .route("/analyze/export", web::post().to(export_analysis_handler))
+            .route("/analyze-history/export", web::post().to(export_analysis_history_handler))
+            .route("/randomness", web::post().to(randomness_handler))
+            .route("/digit-matrix", web::post().to(digit_matrix_handler))
+            .route("/gaps", web::post().to(gaps_handler))
+            .route("/hot-cold", web::post().to(hot_cold_handler))
+            .route("/cooccurrence", web::post().to(cooccurrence_handler))
+            .route("/markov", web::post().to(markov_handler))
+            .route("/permutations", web::post().to(permutations_handler))
+            .route("/monte-carlo", web::post().to(monte_carlo_handler))
+            .route("/calendar", web::post().to(calendar_handler))
+            .route("/generate", web::post().to(generate_handler))
+            .route("/check-tickets", web::post().to(check_tickets_handler))
+            .route("/roi", web::post().to(roi_handler))
+            .route("/wheel", web::post().to(wheel_handler))
+            .route("/prize-tables", web::get().to(prize_tables_handler))
             .service(Files::new("/static", "static").show_files_listing())
     })
     .bind(("0.0.0.0", port))?
     .run()
     .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginate_returns_requested_page() {
+        let items: Vec<usize> = (0..7).collect();
+        assert_eq!(paginate(items.clone(), 1, 3), vec![0, 1, 2]);
+        assert_eq!(paginate(items.clone(), 3, 3), vec![6]);
+        assert!(paginate(items, 4, 3).is_empty());
+    }
+
+    #[test]
+    fn paginate_does_not_overflow_on_huge_pages() {
+        let items: Vec<usize> = (0..7).collect();
+        assert!(paginate(items.clone(), usize::MAX, MAX_DRAWS_PER_PAGE).is_empty());
+        assert!(paginate(items, usize::MAX / 2, 3).is_empty());
+    }
+
+    fn draw(date: Option<&str>, first: &str, last_2: &str) -> ThaiLottoResult {
+        ThaiLottoResult {
+            draw_date: date.map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()),
+            first_prize: first.to_string(),
+            front_3_digits: vec!["123".to_string(), "456".to_string()],
+            last_3_digits: vec!["789".to_string(), "012".to_string()],
+            last_2_digits: last_2.to_string(),
+            second_prizes: vec![],
+            third_prizes: vec![],
+            fourth_prizes: vec![],
+            fifth_prizes: vec![],
+        }
+    }
+
+    fn query(json: serde_json::Value) -> DrawsQuery {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn drawn_within_is_inclusive_and_rejects_undated_draws_when_bounded() {
+        let d = draw(Some("2024-06-16"), "111111", "11");
+        let day = NaiveDate::from_ymd_opt(2024, 6, 16);
+        assert!(d.drawn_within(day, day));
+        assert!(!d.drawn_within(NaiveDate::from_ymd_opt(2024, 6, 17), None));
+        let undated = draw(None, "111111", "11");
+        assert!(undated.drawn_within(None, None));
+        assert!(!undated.drawn_within(None, day));
+    }
+
+    #[test]
+    fn draws_query_filters_by_field_and_suffix() {
+        let results = vec![draw(Some("2024-06-01"), "123456", "56"), draw(Some("2024-06-16"), "654321", "21")];
+        let hits = query(serde_json::json!({"ends_with": "21"})).select(&results);
+        assert_eq!(hits, vec![&results[1]]);
+        // "456" is a front-3 number of both draws, but only the first prize is searched here.
+        let hits = query(serde_json::json!({"field": "first_prize", "contains": "456"})).select(&results);
+        assert_eq!(hits, vec![&results[0]]);
+        let hits = query(serde_json::json!({"contains": "456"})).select(&results);
+        assert_eq!(hits.len(), 2);
+        // Both conditions must hold for the same number.
+        assert!(query(serde_json::json!({"contains": "65", "ends_with": "56"})).select(&results).is_empty());
+    }
+
+    #[test]
+    fn draws_query_sorts_and_bounds_dates() {
+        let results = vec![draw(Some("2024-06-01"), "1", "1"), draw(None, "2", "2"), draw(Some("2024-06-16"), "3", "3")];
+        let desc = query(serde_json::json!({})).select(&results);
+        assert_eq!(desc, vec![&results[2], &results[0], &results[1]]);
+        let asc = query(serde_json::json!({"sort": "date_asc"})).select(&results);
+        assert_eq!(asc, vec![&results[1], &results[0], &results[2]]);
+        let bounded = query(serde_json::json!({"from": "2024-06-02"})).select(&results);
+        assert_eq!(bounded, vec![&results[2]]);
+    }
+
+    fn report(on_conflict: ConflictPolicy) -> ImportReport {
+        ImportReport { dry_run: false, on_conflict, submitted: 0, inserted: Vec::new(), unchanged: 0, conflicts: Vec::new(), rejected: Vec::new() }
+    }
+
+    #[test]
+    fn parse_import_reads_csv_with_bom_and_mixed_separators() {
+        let body = "\u{feff}Draw Date,First Prize,Front 3 Digits,Last 3 Digits,Last 2 Digits\n2567-06-16,123456,\"111, 222\",333 444,56\n";
+        let records = parse_import(ImportFormat::Csv, body.as_bytes()).unwrap();
+        let Ok(parsed) = &records[0] else { panic!("record rejected") };
+        assert_eq!(parsed.front_3_digits, vec!["111", "222"]);
+        assert_eq!(parsed.last_3_digits, vec!["333", "444"]);
+        let (date, _) = validate_import(parsed.clone()).ok().unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2024, 6, 16).unwrap());
+    }
+
+    #[test]
+    fn parse_import_keeps_bad_records_as_rejects() {
+        let csv = "Draw Date,First Prize,Last 2 Digits\nsoon,123456,56\n";
+        assert!(matches!(parse_import(ImportFormat::Csv, csv.as_bytes()).unwrap()[0], Err(ImportRejectReason::Malformed { .. })));
+        let jsonl = "{\"Draw Date\": \"2024-06-16\", \"First Prize\": \"123456\", \"Last 2 Digits\": \"56\"}\n\n{\"First Prize\": 1}\n";
+        let records = parse_import(ImportFormat::Jsonl, jsonl.as_bytes()).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].is_ok() && records[1].is_err());
+        assert!(parse_import(ImportFormat::Json, b"{\"not\": \"an array\"}").is_err());
+    }
+
+    #[test]
+    fn validate_import_rejects_missing_and_misshapen_numbers() {
+        assert!(matches!(validate_import(draw(None, "123456", "56")).err(), Some(ImportRejectReason::MissingDrawDate)));
+        assert!(matches!(validate_import(draw(Some("2024-06-16"), "123456", " ")).err(), Some(ImportRejectReason::MissingField { field: "Last 2 Digits" })));
+        assert!(matches!(validate_import(draw(Some("2024-06-16"), "12345", "56")).err(), Some(ImportRejectReason::InvalidNumbers { field: "First Prize", .. })));
+        let (_, trimmed) = validate_import(draw(Some("2024-06-16"), " 123456 ", "56")).ok().unwrap();
+        assert_eq!(trimmed.first_prize, "123456");
+    }
+
+    #[test]
+    fn accept_import_rejects_repeated_dates_within_an_upload() {
+        let records = vec![Ok(draw(Some("2024-06-16"), "123456", "56")), Ok(draw(Some("2567-06-16"), "654321", "21")), Ok(draw(None, "111111", "11"))];
+        let mut report = report(ConflictPolicy::default());
+        let accepted = accept_import(records, &mut report);
+        assert_eq!(accepted.len(), 1);
+        let rejected: Vec<usize> = report.rejected.iter().map(|r| r.record).collect();
+        assert_eq!(rejected, vec![2, 3]);
+        assert!(matches!(report.rejected[0].reason, ImportRejectReason::DuplicateDate));
+    }
+
+    #[test]
+    fn merge_import_inserts_and_reports_conflicts_by_policy() {
+        let stored = vec![draw(Some("2024-06-01"), "123456", "56")];
+        let accepted = || {
+            ["2024-06-01", "2024-06-16"].iter().zip(["999999", "654321"]).map(|(d, f)| validate_import(draw(Some(d), f, "56")).ok().unwrap()).collect::<Vec<_>>()
+        };
+
+        let mut results = stored.clone();
+        let mut skip = report(ConflictPolicy::Skip);
+        merge_import(&mut results, accepted(), true, &mut skip);
+        assert_eq!(skip.inserted, vec![NaiveDate::from_ymd_opt(2024, 6, 16).unwrap()]);
+        assert_eq!(skip.conflicts[0].fields, vec!["First Prize"]);
+        assert_eq!(results[0].first_prize, "654321", "newest draw sorts first");
+        assert_eq!(results[1], stored[0]);
+
+        let mut replace = report(ConflictPolicy::Replace);
+        merge_import(&mut results, accepted(), true, &mut replace);
+        assert_eq!((replace.inserted.len(), replace.unchanged), (0, 1));
+        assert_eq!(results[1].first_prize, "999999");
+
+        let mut dry_run = report(ConflictPolicy::Replace);
+        let mut untouched = stored.clone();
+        merge_import(&mut untouched, accepted(), false, &mut dry_run);
+        assert_eq!(untouched, stored);
+        assert_eq!((dry_run.inserted.len(), dry_run.conflicts.len()), (1, 1));
+    }
+
+    fn columnar_draws() -> Vec<ThaiLottoResult> {
+        let mut odd = draw(None, "12345", "7");
+        odd.front_3_digits.clear();
+        vec![draw(Some("2024-06-16"), "123456", "07"), odd]
+    }
+
+    #[test]
+    fn draws_record_batch_splits_digits_and_nulls_wrong_widths() {
+        use arrow::array::{Array, Date32Array, UInt8Array};
+        let draws = columnar_draws();
+        let batch = draws_record_batch(&draws.iter().collect::<Vec<_>>()).unwrap();
+        // draw_date, first prize + 6 digits, three-digit lists, last 2 + 2 digits, four lower tiers.
+        assert_eq!(batch.num_columns(), 1 + 7 + 2 + 3 + 4);
+        let dates = batch.column_by_name("draw_date").unwrap().as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(dates.value_as_date(0), NaiveDate::from_ymd_opt(2024, 6, 16));
+        assert!(dates.is_null(1));
+        let digit = |name: &str| batch.column_by_name(name).unwrap().as_any().downcast_ref::<UInt8Array>().unwrap().clone();
+        assert_eq!((digit("first_prize_d1").value(0), digit("first_prize_d6").value(0)), (1, 6));
+        assert_eq!(digit("last_2_digits_d1").value(0), 0, "leading zero is kept");
+        assert!(digit("first_prize_d1").is_null(1) && digit("last_2_digits_d2").is_null(1));
+    }
+
+    #[test]
+    fn columnar_exports_round_trip() {
+        let draws = columnar_draws();
+        let refs: Vec<&ThaiLottoResult> = draws.iter().collect();
+        let batch = draws_record_batch(&refs).unwrap();
+
+        let ipc = export_draws(export::ExportFormat::Arrow, &refs).unwrap();
+        let reader = arrow::ipc::reader::FileReader::try_new(std::io::Cursor::new(ipc), None).unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(read, vec![batch.clone()]);
+
+        let path = std::env::temp_dir().join(format!("draws-round-trip-{}.parquet", std::process::id()));
+        std::fs::write(&path, export_draws(export::ExportFormat::Parquet, &refs).unwrap()).unwrap();
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap().build().unwrap();
+        let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].columns(), batch.columns());
+        assert_eq!(read[0].schema().fields().iter().map(|f| f.name()).collect::<Vec<_>>(), batch.schema().fields().iter().map(|f| f.name()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn analysis_export_neutralizes_rejected_formulas() {
+        let mut numbers: Vec<String> = (10..20).map(|n| n.to_string()).collect();
+        numbers.push("=1+1".to_string());
+        let response = analyze_numbers(&numbers, &validation::ValidationConfig::default(), Locale::En).ok().unwrap();
+        for format in [export::ExportFormat::Csv, export::ExportFormat::ExcelCsv] {
+            let csv = String::from_utf8(export_analysis(format, &response).unwrap()).unwrap();
+            assert!(csv.contains("validation.issues[0].value,'=1+1\n"), "{}", csv);
+        }
+    }
+
+    async fn json_body(response: impl Responder) -> (actix_web::http::StatusCode, serde_json::Value) {
+        let http = actix_web::test::TestRequest::default().to_http_request();
+        let response = response.respond_to(&http);
+        let status = response.status();
+        let body = actix_web::body::to_bytes(response.into_body()).await.ok().unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn hot_endings_narrow_the_allowed_endings() {
+        // "11" and "22" each come up in a third of the draws; every other ending once.
+        let numbers: Vec<String> = (40..50).map(|n| n.to_string()).chain(["11", "22"].iter().cycle().take(20).map(|n| n.to_string())).collect();
+        let generate = |allowed: &[&str]| {
+            let req: GenerateRequest = serde_json::from_value(serde_json::json!({"digits": 6, "count": 10, "seed": 7, "must_end_in_hot": true, "hot_window": 0, "numbers": numbers, "allowed_endings": allowed})).unwrap();
+            generate_handler(actix_web::test::TestRequest::with_uri("/generate?lang=en").to_http_request(), web::Json(req))
+        };
+
+        let (status, body) = json_body(generate(&["22", "45"]).await).await;
+        assert!(status.is_success(), "{}", body);
+        let tickets = body["tickets"].as_array().unwrap();
+        assert!(!tickets.is_empty() && tickets.iter().all(|t| t.as_str().unwrap().ends_with("22")));
+
+        let (status, body) = json_body(generate(&["45"]).await).await;
+        assert_eq!(status, actix_web::http::StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], Message::NoAllowedEndings.text(Locale::En));
+    }
 }
\ No newline at end of file
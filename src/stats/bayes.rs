@@ -0,0 +1,282 @@
+use serde::Serialize;
+use statrs::distribution::{Beta, ContinuousCDF};
+
+use super::{digit_last_seen, digit_rows, frequency_order, top_combinations, DigitColumn};
+use crate::i18n::Message;
+
+/// Symmetric Dirichlet prior concentration per category (1.0 = uniform prior).
+pub const PRIOR_ALPHA: f64 = 1.0;
+
+const CREDIBLE_MASS: f64 = 0.95;
+const PREDICTION_COUNT: usize = 5;
+
+#[derive(Serialize)]
+pub struct CategoryPosterior {
+    pub value: String,
+    pub count: usize,
+    pub posterior_mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Serialize)]
+pub struct DirichletPosterior {
+    pub observations: usize,
+    pub uniform_probability: f64,
+    pub categories: Vec<CategoryPosterior>,
+    /// Largest absolute difference between a posterior mean and the uniform probability.
+    pub max_shift_from_uniform: f64,
+    /// KL divergence (nats) of the posterior mean distribution from uniform.
+    pub kl_divergence_from_uniform: f64,
+}
+
+#[derive(Serialize)]
+pub struct PositionPosterior {
+    pub position: usize,
+    pub posterior: DirichletPosterior,
+}
+
+#[derive(Serialize)]
+pub struct BayesPrediction {
+    pub number: String,
+    /// Posterior predictive probability of drawing exactly this number.
+    pub probability: f64,
+}
+
+#[derive(Serialize)]
+pub struct BayesReport {
+    pub sample_size: usize,
+    pub prior_alpha: f64,
+    pub credible_mass: f64,
+    pub positions: Vec<PositionPosterior>,
+    /// Posterior over every two-digit ending 00-99; `None` for single-digit series.
+    pub endings: Option<DirichletPosterior>,
+    /// Most probable next numbers, best first.
+    pub predictions: Vec<BayesPrediction>,
+}
+
+/// Dirichlet-multinomial posterior per digit position and per two-digit ending.
+/// Series of up to two digits are predicted as whole values; longer ones from the
+/// per-position posteriors.
+pub fn bayes_analysis(series: &[String]) -> Result<BayesReport, Message> {
+    if series.is_empty() {
+        return Err(Message::InsufficientAnalysisData {
+            required: 1,
+            found: 0,
+        });
+    }
+    let width = series[0].len();
+    let rows = digit_rows(series);
+
+    let positions: Vec<PositionPosterior> = (0..width)
+        .map(|pos| {
+            let mut counts = vec![0usize; 10];
+            for row in &rows {
+                counts[row[pos] as usize] += 1;
+            }
+            PositionPosterior {
+                position: pos + 1,
+                posterior: dirichlet_posterior(&counts, |digit| digit.to_string()),
+            }
+        })
+        .collect();
+
+    let endings = (width >= 2).then(|| {
+        let mut counts = vec![0usize; 100];
+        for row in &rows {
+            counts[row[width - 2] as usize * 10 + row[width - 1] as usize] += 1;
+        }
+        dirichlet_posterior(&counts, |ending| format!("{:02}", ending))
+    });
+
+    let predictions = match (&endings, width) {
+        (Some(endings), 2) => {
+            // Posterior means rise with the count, so rank as any frequency table.
+            let mut last_seen = [None; 100];
+            for (i, row) in rows.iter().enumerate() {
+                last_seen[row[0] as usize * 10 + row[1] as usize] = Some(i);
+            }
+            let mut ranked: Vec<(usize, &CategoryPosterior)> =
+                endings.categories.iter().enumerate().collect();
+            ranked.sort_by(|(i, a), (j, b)| {
+                frequency_order(
+                    (a.count, last_seen[*i], &a.value),
+                    (b.count, last_seen[*j], &b.value),
+                )
+            });
+            ranked
+                .into_iter()
+                .take(PREDICTION_COUNT)
+                .map(|(_, c)| BayesPrediction {
+                    number: c.value.clone(),
+                    probability: c.posterior_mean,
+                })
+                .collect()
+        }
+        _ => {
+            let means: Vec<Vec<f64>> = positions
+                .iter()
+                .map(|p| {
+                    p.posterior
+                        .categories
+                        .iter()
+                        .map(|c| c.posterior_mean)
+                        .collect()
+                })
+                .collect();
+            let columns: Vec<DigitColumn> = means
+                .iter()
+                .enumerate()
+                .map(|(pos, probabilities)| DigitColumn {
+                    probabilities,
+                    last_seen: digit_last_seen(&rows, pos),
+                })
+                .collect();
+            top_combinations(&columns, PREDICTION_COUNT)
+                .into_iter()
+                .map(|(number, probability)| BayesPrediction {
+                    number,
+                    probability,
+                })
+                .collect()
+        }
+    };
+
+    Ok(BayesReport {
+        sample_size: rows.len(),
+        prior_alpha: PRIOR_ALPHA,
+        credible_mass: CREDIBLE_MASS,
+        positions,
+        endings,
+        predictions,
+    })
+}
+
+fn dirichlet_posterior(counts: &[usize], label: impl Fn(usize) -> String) -> DirichletPosterior {
+    let k = counts.len() as f64;
+    let observations: usize = counts.iter().sum();
+    let alpha_total = observations as f64 + k * PRIOR_ALPHA;
+    let uniform = 1.0 / k;
+    let tail = (1.0 - CREDIBLE_MASS) / 2.0;
+
+    let categories: Vec<CategoryPosterior> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            // Each marginal of a Dirichlet posterior is Beta(α_i, α_0 − α_i).
+            let a = count as f64 + PRIOR_ALPHA;
+            let marginal = Beta::new(a, alpha_total - a).unwrap();
+            CategoryPosterior {
+                value: label(i),
+                count,
+                posterior_mean: a / alpha_total,
+                lower: beta_quantile(&marginal, tail),
+                upper: beta_quantile(&marginal, 1.0 - tail),
+            }
+        })
+        .collect();
+
+    let max_shift_from_uniform = categories
+        .iter()
+        .map(|c| (c.posterior_mean - uniform).abs())
+        .fold(0.0, f64::max);
+    let kl_divergence_from_uniform = categories
+        .iter()
+        .map(|c| c.posterior_mean * (c.posterior_mean / uniform).ln())
+        .sum();
+
+    DirichletPosterior {
+        observations,
+        uniform_probability: uniform,
+        categories,
+        max_shift_from_uniform,
+        kl_divergence_from_uniform,
+    }
+}
+
+/// Bisection on [0, 1]; statrs' default inverse CDF stops at ~1e-4 precision, which is
+/// too coarse for probabilities around 0.01.
+fn beta_quantile(distribution: &Beta, p: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..60 {
+        let mid = (low + high) / 2.0;
+        if distribution.cdf(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn beta_quantiles_match_closed_forms() {
+        let uniform = Beta::new(1.0, 1.0).unwrap();
+        assert!((beta_quantile(&uniform, 0.025) - 0.025).abs() < 1e-12);
+        // CDF 1 − (1 − x)^9 and x², inverted by hand.
+        let skewed = Beta::new(1.0, 9.0).unwrap();
+        let expected = 1.0 - 0.025f64.powf(1.0 / 9.0);
+        assert!((beta_quantile(&skewed, 0.975) - expected).abs() < 1e-12);
+        let rising = Beta::new(2.0, 1.0).unwrap();
+        assert!((beta_quantile(&rising, 0.25) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn posterior_adds_the_prior_to_every_count() {
+        let mut counts = vec![0usize; 10];
+        counts[0] = 3;
+        let posterior = dirichlet_posterior(&counts, |i| i.to_string());
+        assert_eq!(posterior.observations, 3);
+        assert!((posterior.categories[0].posterior_mean - 4.0 / 13.0).abs() < 1e-12);
+        assert!((posterior.categories[1].posterior_mean - 1.0 / 13.0).abs() < 1e-12);
+        let total: f64 = posterior.categories.iter().map(|c| c.posterior_mean).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert!((posterior.max_shift_from_uniform - (4.0 / 13.0 - 0.1)).abs() < 1e-12);
+        for c in &posterior.categories {
+            assert!(c.lower < c.posterior_mean && c.posterior_mean < c.upper);
+        }
+    }
+
+    #[test]
+    fn no_observations_leave_the_prior_uniform() {
+        let posterior = dirichlet_posterior(&[0; 10], |i| i.to_string());
+        assert_eq!(posterior.max_shift_from_uniform, 0.0);
+        assert!(posterior.kl_divergence_from_uniform.abs() < 1e-12);
+        let interval = &posterior.categories[0];
+        assert!((interval.lower - (1.0 - 0.975f64.powf(1.0 / 9.0))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn two_digit_predictions_break_ties_by_recency() {
+        let report = bayes_analysis(&series(&["05", "12", "05", "12", "31"])).unwrap();
+        let numbers: Vec<&str> = report
+            .predictions
+            .iter()
+            .map(|p| p.number.as_str())
+            .collect();
+        assert_eq!(numbers[..3], ["12", "05", "31"]);
+        assert!((report.predictions[0].probability - 3.0 / 105.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wider_series_predict_from_positions() {
+        let report = bayes_analysis(&series(&["123", "123", "456"])).unwrap();
+        assert_eq!(report.positions.len(), 3);
+        assert_eq!(report.endings.as_ref().unwrap().categories.len(), 100);
+        assert_eq!(report.predictions.len(), PREDICTION_COUNT);
+        assert_eq!(report.predictions[0].number, "123");
+        assert!(bayes_analysis(&series(&["1", "2"]))
+            .unwrap()
+            .endings
+            .is_none());
+        assert!(bayes_analysis(&[]).is_err());
+    }
+}
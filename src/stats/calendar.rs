@@ -0,0 +1,236 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use std::collections::BTreeMap;
+
+use super::frequency_order;
+use super::randomness::ALPHA;
+use crate::i18n::Message;
+
+/// Offset between the Gregorian and Thai Buddhist calendars (พ.ศ. = ค.ศ. + 543).
+pub const BUDDHIST_ERA_OFFSET: i32 = 543;
+
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Grouping {
+    /// Regular draws on the 1st against those on the 16th.
+    DrawDay,
+    Month,
+    BuddhistYear,
+    /// Regular (1st/16th) against off-schedule draws such as 17 Jan, 2 May or 30 Dec.
+    Schedule,
+}
+
+#[derive(Serialize)]
+pub struct GroupSummary {
+    pub group: String,
+    pub draws: usize,
+    /// Occurrences of each digit 0-9 across every number and position in the group.
+    pub digit_counts: Vec<usize>,
+    /// Most frequent digit; ties follow [`frequency_order`].
+    pub top_digit: u8,
+}
+
+#[derive(Serialize)]
+pub struct GroupingResult {
+    pub grouping: Grouping,
+    pub groups: Vec<GroupSummary>,
+    /// Chi-square test of homogeneity of the digit distribution across groups.
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    /// `None` when fewer than two groups have data.
+    pub p_value: Option<f64>,
+    pub significant: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct CalendarReport {
+    pub draws: usize,
+    pub groupings: Vec<GroupingResult>,
+}
+
+/// Whether a draw fell on one of the regular draw days (1st or 16th of the month).
+pub fn is_regular_draw(date: NaiveDate) -> bool {
+    date.day() == 1 || date.day() == 16
+}
+
+/// Digit tallies of the draws in one group.
+struct GroupCounts {
+    label: String,
+    draws: usize,
+    digits: [usize; 10],
+    /// Index of the latest draw with each digit, in date order.
+    last_seen: [Option<usize>; 10],
+}
+
+/// Digit distributions of the numbers drawn, grouped by calendar features of the draw
+/// date. Each draw is `(date, numbers)`, in any order; numbers with non-digit characters
+/// are ignored.
+pub fn calendar_analysis(draws: &[(NaiveDate, Vec<String>)]) -> Result<CalendarReport, Message> {
+    let mut draws: Vec<(NaiveDate, [usize; 10])> = draws
+        .iter()
+        .map(|(date, numbers)| {
+            let mut counts = [0usize; 10];
+            for number in numbers
+                .iter()
+                .filter(|n| n.chars().all(|c| c.is_ascii_digit()))
+            {
+                for b in number.bytes() {
+                    counts[(b - b'0') as usize] += 1;
+                }
+            }
+            (*date, counts)
+        })
+        .filter(|(_, counts)| counts.iter().any(|&c| c > 0))
+        .collect();
+    if draws.len() < 10 {
+        return Err(Message::InsufficientDraws {
+            required: 10,
+            found: draws.len(),
+        });
+    }
+    draws.sort_by_key(|(date, _)| *date);
+
+    let group_by = |grouping: Grouping, key: &dyn Fn(NaiveDate) -> Option<(i32, String)>| {
+        let mut groups: BTreeMap<i32, GroupCounts> = BTreeMap::new();
+        for (i, (date, counts)) in draws.iter().enumerate() {
+            if let Some((order, label)) = key(*date) {
+                let group = groups.entry(order).or_insert(GroupCounts {
+                    label,
+                    draws: 0,
+                    digits: [0; 10],
+                    last_seen: [None; 10],
+                });
+                group.draws += 1;
+                for (digit, &count) in counts.iter().enumerate() {
+                    group.digits[digit] += count;
+                    if count > 0 {
+                        group.last_seen[digit] = Some(i);
+                    }
+                }
+            }
+        }
+        homogeneity_test(grouping, groups.into_values().collect())
+    };
+
+    let groupings = vec![
+        group_by(Grouping::DrawDay, &|date| match date.day() {
+            1 => Some((1, "1".to_string())),
+            16 => Some((16, "16".to_string())),
+            _ => None,
+        }),
+        group_by(Grouping::Month, &|date| {
+            Some((date.month() as i32, date.month().to_string()))
+        }),
+        group_by(Grouping::BuddhistYear, &|date| {
+            let year = date.year() + BUDDHIST_ERA_OFFSET;
+            Some((year, year.to_string()))
+        }),
+        group_by(Grouping::Schedule, &|date| {
+            Some(if is_regular_draw(date) {
+                (0, "regular".to_string())
+            } else {
+                (1, "special".to_string())
+            })
+        }),
+    ];
+
+    Ok(CalendarReport {
+        draws: draws.len(),
+        groupings,
+    })
+}
+
+fn homogeneity_test(grouping: Grouping, groups: Vec<GroupCounts>) -> GroupingResult {
+    let column_totals: Vec<usize> = (0..10)
+        .map(|digit| groups.iter().map(|group| group.digits[digit]).sum())
+        .collect();
+    let grand_total: usize = column_totals.iter().sum();
+    let used_columns = column_totals.iter().filter(|&&t| t > 0).count();
+
+    let mut chi_square = 0.0;
+    for group in &groups {
+        let row_total: usize = group.digits.iter().sum();
+        for (digit, &observed) in group.digits.iter().enumerate() {
+            if column_totals[digit] == 0 {
+                continue;
+            }
+            let expected = row_total as f64 * column_totals[digit] as f64 / grand_total as f64;
+            chi_square += (observed as f64 - expected).powi(2) / expected;
+        }
+    }
+    let degrees_of_freedom = groups.len().saturating_sub(1) * used_columns.saturating_sub(1);
+    let p_value = (degrees_of_freedom > 0).then(|| {
+        ChiSquared::new(degrees_of_freedom as f64)
+            .unwrap()
+            .sf(chi_square)
+    });
+
+    GroupingResult {
+        grouping,
+        groups: groups
+            .into_iter()
+            .map(|group| GroupSummary {
+                top_digit: (0..10u8)
+                    .min_by(|&a, &b| {
+                        let key =
+                            |d: u8| (group.digits[d as usize], group.last_seen[d as usize], d);
+                        frequency_order(key(a), key(b))
+                    })
+                    .unwrap_or(0),
+                group: group.label,
+                draws: group.draws,
+                digit_counts: group.digits.to_vec(),
+            })
+            .collect(),
+        chi_square,
+        degrees_of_freedom,
+        p_value,
+        significant: p_value.map(|p| p < ALPHA),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(y: i32, m: u32, d: u32, number: &str) -> (NaiveDate, Vec<String>) {
+        (
+            NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+            vec![number.to_string()],
+        )
+    }
+
+    #[test]
+    fn requires_ten_dated_draws() {
+        let draws = vec![draw(2024, 1, 1, "12"); 9];
+        assert!(calendar_analysis(&draws).is_err());
+    }
+
+    #[test]
+    fn top_digit_ties_go_to_the_most_recent_digit() {
+        // Given newest first; digits 1 and 8 tie, and 8 appeared last.
+        let mut draws: Vec<_> = (1..=10)
+            .rev()
+            .map(|m| draw(2024, m, 1, if m % 2 == 0 { "81" } else { "18" }))
+            .collect();
+        draws[0] = draw(2024, 10, 1, "88");
+        draws[1] = draw(2024, 9, 1, "11");
+        let report = calendar_analysis(&draws).unwrap();
+        let schedule = report
+            .groupings
+            .iter()
+            .find(|g| matches!(g.grouping, Grouping::Schedule))
+            .unwrap();
+        assert_eq!(schedule.groups[0].top_digit, 8);
+        assert_eq!(schedule.groups[0].draws, 10);
+    }
+
+    #[test]
+    fn regular_draws_fall_on_the_first_and_sixteenth() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        assert!(is_regular_draw(date(1)));
+        assert!(is_regular_draw(date(16)));
+        assert!(!is_regular_draw(date(2)));
+    }
+}
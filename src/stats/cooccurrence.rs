@@ -0,0 +1,296 @@
+use serde::Serialize;
+use statrs::distribution::{DiscreteCDF, Poisson};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::i18n::Message;
+
+/// Family-wise significance level; each analysis is Bonferroni-corrected on its own.
+const ALPHA: f64 = 0.05;
+
+#[derive(Serialize)]
+pub struct CoOccurrence {
+    pub left: String,
+    pub right: String,
+    /// Draws in which both sides appeared.
+    pub count: usize,
+    /// Count expected if the two sides were independent, from their marginal frequencies.
+    pub expected: f64,
+    pub lift: f64,
+    /// One-sided Poisson probability of seeing `count` or more by chance.
+    pub p_value: f64,
+    /// Whether `p_value` survives a Bonferroni correction over the whole analysis.
+    pub significant: bool,
+}
+
+#[derive(Serialize)]
+pub struct RecurringPattern {
+    pub digits: String,
+    /// Draws containing every digit of the pattern.
+    pub occurrences: usize,
+    /// Consecutive draw pairs in which both draws contain the pattern.
+    pub recurrences: usize,
+    pub expected: f64,
+    pub lift: f64,
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+#[derive(Serialize)]
+pub struct CoOccurrenceReport {
+    pub draws: usize,
+    /// Last-2 value against the final digit of the first prize.
+    pub last_2_by_first_prize_ending: Vec<CoOccurrence>,
+    /// Digits present in the last-2 against digits present in the first prize.
+    pub last_2_digits_by_first_prize_digits: Vec<CoOccurrence>,
+    /// First-prize digit pairs that appear in two consecutive draws.
+    pub consecutive_digit_pairs: Vec<RecurringPattern>,
+    /// First-prize digit triples that appear in two consecutive draws.
+    pub consecutive_digit_triples: Vec<RecurringPattern>,
+}
+
+/// Co-occurrence of prize fields within a draw and of digit patterns across consecutive
+/// draws. `draws` holds `(first_prize, last_2_digits)` pairs, oldest first; draws whose
+/// fields are not 6 and 2 digits are skipped. Results are sorted by p-value.
+pub fn cooccurrence_analysis(draws: &[(&str, &str)]) -> Result<CoOccurrenceReport, Message> {
+    let is_digits = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+    let draws: Vec<(&str, &str)> = draws
+        .iter()
+        .map(|(fp, l2)| (fp.trim(), l2.trim()))
+        .filter(|(fp, l2)| is_digits(fp, 6) && is_digits(l2, 2))
+        .collect();
+    if draws.len() < 10 {
+        return Err(Message::InsufficientDraws {
+            required: 10,
+            found: draws.len(),
+        });
+    }
+
+    let digit_set = |s: &str| s.chars().map(String::from).collect::<BTreeSet<String>>();
+    let endings: Vec<BTreeSet<String>> = draws
+        .iter()
+        .map(|(fp, _)| BTreeSet::from([fp[5..].to_string()]))
+        .collect();
+    let last_2_values: Vec<BTreeSet<String>> = draws
+        .iter()
+        .map(|(_, l2)| BTreeSet::from([l2.to_string()]))
+        .collect();
+    let first_prize_digits: Vec<BTreeSet<String>> =
+        draws.iter().map(|(fp, _)| digit_set(fp)).collect();
+    let last_2_digits: Vec<BTreeSet<String>> = draws.iter().map(|(_, l2)| digit_set(l2)).collect();
+
+    Ok(CoOccurrenceReport {
+        draws: draws.len(),
+        last_2_by_first_prize_ending: cross_field(&last_2_values, &endings, 100 * 10),
+        last_2_digits_by_first_prize_digits: cross_field(
+            &last_2_digits,
+            &first_prize_digits,
+            10 * 10,
+        ),
+        consecutive_digit_pairs: consecutive_patterns(&draws, 2),
+        consecutive_digit_triples: consecutive_patterns(&draws, 3),
+    })
+}
+
+fn cross_field(
+    left: &[BTreeSet<String>],
+    right: &[BTreeSet<String>],
+    comparisons: usize,
+) -> Vec<CoOccurrence> {
+    let n = left.len() as f64;
+    let mut left_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut right_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut joint_counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for (l_set, r_set) in left.iter().zip(right) {
+        for l in l_set {
+            *left_counts.entry(l).or_default() += 1;
+        }
+        for r in r_set {
+            *right_counts.entry(r).or_default() += 1;
+        }
+        for l in l_set {
+            for r in r_set {
+                *joint_counts.entry((l, r)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut results: Vec<CoOccurrence> = joint_counts
+        .into_iter()
+        .map(|((l, r), count)| {
+            let expected = left_counts[l] as f64 * right_counts[r] as f64 / n;
+            let p_value = poisson_upper_tail(count, expected);
+            CoOccurrence {
+                left: l.to_string(),
+                right: r.to_string(),
+                count,
+                expected,
+                lift: count as f64 / expected,
+                p_value,
+                significant: p_value * (comparisons as f64) < ALPHA,
+            }
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        a.p_value
+            .total_cmp(&b.p_value)
+            .then(b.lift.total_cmp(&a.lift))
+    });
+    results
+}
+
+fn consecutive_patterns(draws: &[(&str, &str)], size: usize) -> Vec<RecurringPattern> {
+    let digit_sets: Vec<[bool; 10]> = draws
+        .iter()
+        .map(|(fp, _)| {
+            let mut present = [false; 10];
+            for b in fp.bytes() {
+                present[(b - b'0') as usize] = true;
+            }
+            present
+        })
+        .collect();
+    let patterns = combinations(size);
+    let transitions = (draws.len() - 1) as f64;
+    let n = draws.len() as f64;
+
+    let mut results: Vec<RecurringPattern> = patterns
+        .iter()
+        .map(|pattern| {
+            let contains: Vec<bool> = digit_sets
+                .iter()
+                .map(|set| pattern.iter().all(|&d| set[d]))
+                .collect();
+            let occurrences = contains.iter().filter(|&&c| c).count();
+            let recurrences = contains.windows(2).filter(|w| w[0] && w[1]).count();
+            let share = occurrences as f64 / n;
+            let expected = transitions * share * share;
+            let p_value = poisson_upper_tail(recurrences, expected);
+            RecurringPattern {
+                digits: pattern.iter().map(|d| d.to_string()).collect(),
+                occurrences,
+                recurrences,
+                expected,
+                lift: if expected > 0.0 {
+                    recurrences as f64 / expected
+                } else {
+                    0.0
+                },
+                p_value,
+                significant: p_value * (patterns.len() as f64) < ALPHA,
+            }
+        })
+        .filter(|pattern| pattern.recurrences > 0)
+        .collect();
+    results.sort_by(|a, b| {
+        a.p_value
+            .total_cmp(&b.p_value)
+            .then(b.lift.total_cmp(&a.lift))
+    });
+    results
+}
+
+/// All ascending `size`-element combinations of the digits 0-9.
+fn combinations(size: usize) -> Vec<Vec<usize>> {
+    let mut result = vec![Vec::new()];
+    for _ in 0..size {
+        result = result
+            .into_iter()
+            .flat_map(|combo: Vec<usize>| {
+                let start = combo.last().map_or(0, |&d| d + 1);
+                (start..10).map(move |d| {
+                    let mut next = combo.clone();
+                    next.push(d);
+                    next
+                })
+            })
+            .collect();
+    }
+    result
+}
+
+/// P(X ≥ k) for X ~ Poisson(λ).
+fn poisson_upper_tail(k: usize, lambda: f64) -> f64 {
+    if k == 0 {
+        return 1.0;
+    }
+    if lambda <= 0.0 {
+        return 0.0;
+    }
+    Poisson::new(lambda).unwrap().sf(k as u64 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerates_digit_combinations() {
+        let pairs = combinations(2);
+        assert_eq!(pairs.len(), 45);
+        assert_eq!(pairs[0], vec![0, 1]);
+        let triples = combinations(3);
+        assert_eq!(triples.len(), 120);
+        assert_eq!(triples.last(), Some(&vec![7, 8, 9]));
+    }
+
+    #[test]
+    fn poisson_tail_matches_closed_form() {
+        assert_eq!(poisson_upper_tail(0, 3.0), 1.0);
+        assert_eq!(poisson_upper_tail(2, 0.0), 0.0);
+        let e = (-1f64).exp();
+        assert!((poisson_upper_tail(1, 1.0) - (1.0 - e)).abs() < 1e-12);
+        assert!((poisson_upper_tail(2, 1.0) - (1.0 - 2.0 * e)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pairs_last_2_with_the_first_prize_ending() {
+        let owned: Vec<(String, String)> = (0..20)
+            .map(|i| (format!("12345{}", i % 10), format!("{0}{0}", i % 10)))
+            .collect();
+        let draws: Vec<(&str, &str)> = owned
+            .iter()
+            .map(|(fp, l2)| (fp.as_str(), l2.as_str()))
+            .collect();
+        let report = cooccurrence_analysis(&draws).unwrap();
+        assert_eq!(report.draws, 20);
+        let top = &report.last_2_by_first_prize_ending[0];
+        assert_eq!(top.left, format!("{0}{0}", top.right));
+        assert_eq!(top.count, 2);
+        assert!((top.expected - 0.2).abs() < 1e-12);
+        assert!((top.lift - 10.0).abs() < 1e-9);
+        // Bonferroni over 1000 comparisons keeps a 1.75% tail from being significant.
+        assert!(!top.significant);
+        assert!(report
+            .last_2_by_first_prize_ending
+            .windows(2)
+            .all(|w| w[0].p_value <= w[1].p_value));
+    }
+
+    #[test]
+    fn counts_patterns_recurring_in_consecutive_draws() {
+        let draws = vec![("123456", "12"); 12];
+        let report = cooccurrence_analysis(&draws).unwrap();
+        // Only pairs of the digits 1-6 ever appear.
+        assert_eq!(report.consecutive_digit_pairs.len(), 15);
+        let pair = &report.consecutive_digit_pairs[0];
+        assert_eq!(pair.occurrences, 12);
+        assert_eq!(pair.recurrences, 11);
+        assert!((pair.expected - 11.0).abs() < 1e-12);
+        assert!(!pair.significant);
+        assert_eq!(report.consecutive_digit_triples.len(), 20);
+    }
+
+    #[test]
+    fn skips_malformed_draws_before_counting() {
+        let mut draws = vec![("123456", "12"); 9];
+        draws.push(("12345", "12"));
+        draws.push(("123456", "1x"));
+        assert!(matches!(
+            cooccurrence_analysis(&draws).err(),
+            Some(Message::InsufficientDraws {
+                required: 10,
+                found: 9
+            })
+        ));
+    }
+}
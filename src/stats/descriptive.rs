@@ -0,0 +1,250 @@
+use serde::Serialize;
+
+use super::rank_by_frequency;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Percentile {
+    pub percentile: f64,
+    pub value: f64,
+}
+
+/// Sample moments and order statistics of a numeric series. Moments that are
+/// undefined for the input (too few values, zero spread) are `None`.
+#[derive(Serialize, Clone, Debug)]
+pub struct DescriptiveStats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub range: f64,
+    /// Sample (n - 1) variance.
+    pub variance: Option<f64>,
+    pub std_dev: Option<f64>,
+    /// Adjusted Fisher–Pearson skewness G1.
+    pub skewness: Option<f64>,
+    /// Sample excess kurtosis G2 (0 for a normal distribution).
+    pub excess_kurtosis: Option<f64>,
+    pub q1: f64,
+    pub q3: f64,
+    pub iqr: f64,
+    pub percentiles: Vec<Percentile>,
+    /// Standard deviation divided by the mean.
+    pub coefficient_of_variation: Option<f64>,
+}
+
+/// A series of same-width lottery numbers read as integers on their own scale,
+/// 0 to 10^digit_width - 1, so "05" and "000005" are never compared directly.
+#[derive(Serialize, Clone, Debug)]
+pub struct NumericSummary {
+    pub digit_width: usize,
+    pub scale_max: u64,
+    /// Mean as a share of `scale_max`; about 0.5 for uniform draws of any width.
+    pub relative_mean: f64,
+    #[serde(flatten)]
+    pub stats: DescriptiveStats,
+}
+
+/// Frequencies of a same-width series with each number treated as a label.
+#[derive(Serialize, Clone, Debug)]
+pub struct CategoricalSummary {
+    pub digit_width: usize,
+    pub count: usize,
+    pub distinct_values: usize,
+    /// 10^digit_width.
+    pub possible_values: u64,
+    pub mode: String,
+    pub mode_count: usize,
+    /// Shannon entropy of the observed frequencies, in bits.
+    pub entropy_bits: f64,
+    /// Entropy of a uniform draw over every possible value.
+    pub max_entropy_bits: f64,
+}
+
+const REPORTED_PERCENTILES: [f64; 7] = [5.0, 10.0, 25.0, 50.0, 75.0, 90.0, 95.0];
+
+pub fn describe(values: &[f64]) -> Option<DescriptiveStats> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let central_moment = |k: i32| values.iter().map(|v| (v - mean).powi(k)).sum::<f64>() / n;
+    let m2 = central_moment(2);
+
+    let variance = (values.len() > 1).then(|| m2 * n / (n - 1.0));
+    let std_dev = variance.map(f64::sqrt);
+    let skewness = (values.len() > 2 && m2 > 0.0).then(|| {
+        let g1 = central_moment(3) / m2.powf(1.5);
+        g1 * (n * (n - 1.0)).sqrt() / (n - 2.0)
+    });
+    let excess_kurtosis = (values.len() > 3 && m2 > 0.0).then(|| {
+        let g2 = central_moment(4) / (m2 * m2) - 3.0;
+        (n - 1.0) / ((n - 2.0) * (n - 3.0)) * ((n + 1.0) * g2 + 6.0)
+    });
+    let coefficient_of_variation = std_dev.filter(|_| mean != 0.0).map(|sd| sd / mean);
+
+    let q1 = percentile(&sorted, 25.0);
+    let q3 = percentile(&sorted, 75.0);
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+
+    Some(DescriptiveStats {
+        count: values.len(),
+        mean,
+        median: percentile(&sorted, 50.0),
+        min,
+        max,
+        range: max - min,
+        variance,
+        std_dev,
+        skewness,
+        excess_kurtosis,
+        q1,
+        q3,
+        iqr: q3 - q1,
+        percentiles: REPORTED_PERCENTILES
+            .iter()
+            .map(|&p| Percentile {
+                percentile: p,
+                value: percentile(&sorted, p),
+            })
+            .collect(),
+        coefficient_of_variation,
+    })
+}
+
+/// Linearly interpolated percentile (`p` in 0-100) of an ascending, non-empty slice.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Width of a non-empty series of digit strings that all share one width.
+fn series_width(series: &[&str]) -> Option<usize> {
+    let width = series.first()?.len();
+    let uniform = series
+        .iter()
+        .all(|s| s.len() == width && s.chars().all(|c| c.is_ascii_digit()));
+    (width > 0 && uniform).then_some(width)
+}
+
+/// `None` unless every entry is a digit string of the same width.
+pub fn describe_numeric(series: &[&str]) -> Option<NumericSummary> {
+    let digit_width = series_width(series)?;
+    let values: Vec<f64> = series
+        .iter()
+        .map(|s| s.parse::<u64>().map(|v| v as f64))
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let scale_max = 10u64.checked_pow(digit_width as u32)? - 1;
+    let stats = describe(&values)?;
+    Some(NumericSummary {
+        digit_width,
+        scale_max,
+        relative_mean: if scale_max > 0 {
+            stats.mean / scale_max as f64
+        } else {
+            0.0
+        },
+        stats,
+    })
+}
+
+/// `None` unless every entry is a digit string of the same width. Ties for the mode
+/// follow [`super::frequency_order`].
+pub fn describe_categorical(series: &[&str]) -> Option<CategoricalSummary> {
+    let digit_width = series_width(series)?;
+    let ranks = rank_by_frequency(series);
+    let mode = ranks.first()?;
+    let n = series.len() as f64;
+    let entropy_bits = -ranks
+        .iter()
+        .map(|r| {
+            let p = r.count as f64 / n;
+            p * p.log2()
+        })
+        .sum::<f64>();
+    Some(CategoricalSummary {
+        digit_width,
+        count: series.len(),
+        distinct_values: ranks.len(),
+        possible_values: 10u64.pow(digit_width.min(19) as u32),
+        mode: mode.value.to_string(),
+        mode_count: mode.count,
+        entropy_bits,
+        max_entropy_bits: digit_width as f64 * 10f64.log2(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn describes_moments_and_quartiles() {
+        let stats = describe(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.count, 8);
+        assert!(close(stats.mean, 5.0));
+        assert!(close(stats.median, 4.5));
+        assert!(close(stats.variance.unwrap(), 32.0 / 7.0));
+        assert!(close(stats.q1, 4.0));
+        assert!(close(stats.q3, 5.5));
+        assert!(close(stats.iqr, 1.5));
+        assert!(close(stats.range, 7.0));
+        assert!(close(stats.skewness.unwrap(), 0.818_487_553_356_799_7));
+        assert!(close(stats.excess_kurtosis.unwrap(), 0.940_625));
+        assert_eq!(stats.percentiles.len(), REPORTED_PERCENTILES.len());
+    }
+
+    #[test]
+    fn undefined_moments_are_none() {
+        assert!(describe(&[]).is_none());
+        let single = describe(&[3.0]).unwrap();
+        assert!(single.variance.is_none() && single.skewness.is_none());
+        let constant = describe(&[3.0; 5]).unwrap();
+        assert_eq!(constant.variance, Some(0.0));
+        assert!(constant.skewness.is_none() && constant.excess_kurtosis.is_none());
+        let zero_mean = describe(&[-1.0, 1.0]).unwrap();
+        assert!(zero_mean.coefficient_of_variation.is_none());
+    }
+
+    #[test]
+    fn percentile_interpolates_and_clamps() {
+        let sorted = [10.0, 20.0, 30.0];
+        assert!(close(percentile(&sorted, 25.0), 15.0));
+        assert!(close(percentile(&sorted, 150.0), 30.0));
+        assert!(close(percentile(&sorted, -5.0), 10.0));
+    }
+
+    #[test]
+    fn numeric_summary_uses_the_series_own_scale() {
+        let summary = describe_numeric(&["05", "95"]).unwrap();
+        assert_eq!(summary.digit_width, 2);
+        assert_eq!(summary.scale_max, 99);
+        assert!(close(summary.relative_mean, 50.0 / 99.0));
+        assert!(describe_numeric(&["05", "123"]).is_none());
+        assert!(describe_numeric(&["5a"]).is_none());
+        assert!(describe_numeric(&[]).is_none());
+    }
+
+    #[test]
+    fn categorical_summary_counts_labels() {
+        let summary = describe_categorical(&["05", "12", "05"]).unwrap();
+        assert_eq!(summary.mode, "05");
+        assert_eq!(summary.mode_count, 2);
+        assert_eq!(summary.distinct_values, 2);
+        assert_eq!(summary.possible_values, 100);
+        assert!(close(summary.entropy_bits, 0.918_295_834_054_489_6));
+        assert!(close(summary.max_entropy_bits, 2.0 * 10f64.log2()));
+    }
+}
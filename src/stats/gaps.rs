@@ -0,0 +1,154 @@
+use serde::Serialize;
+
+use super::digit_rows;
+use crate::i18n::Message;
+
+#[derive(Serialize)]
+pub struct GapStats {
+    pub value: String,
+    pub appearances: usize,
+    /// Draws since the value last appeared; equals the sample size if it never did.
+    pub current_gap: usize,
+    /// Longest run of draws without the value, including the current one.
+    pub longest_gap: usize,
+    /// Mean number of draws between consecutive appearances.
+    pub mean_gap: Option<f64>,
+    /// Chance of a gap at least as long as `current_gap` if draws are uniform.
+    pub current_gap_probability: f64,
+}
+
+#[derive(Serialize)]
+pub struct PositionGaps {
+    pub position: usize,
+    pub digits: Vec<GapStats>,
+}
+
+#[derive(Serialize)]
+pub struct GapReport {
+    pub sample_size: usize,
+    /// One entry per two-digit ending 00-99; empty for single-digit series.
+    pub endings: Vec<GapStats>,
+    pub positions: Vec<PositionGaps>,
+}
+
+/// Overdue ("เลขไม่ออกนาน") analysis of a chronologically ordered series (oldest first).
+pub fn gap_analysis(series: &[String]) -> Result<GapReport, Message> {
+    if series.is_empty() {
+        return Err(Message::InsufficientAnalysisData {
+            required: 1,
+            found: 0,
+        });
+    }
+    let width = series[0].len();
+    let rows = digit_rows(series);
+
+    let endings = if width >= 2 {
+        let ending_values: Vec<usize> = rows
+            .iter()
+            .map(|row| row[width - 2] as usize * 10 + row[width - 1] as usize)
+            .collect();
+        (0..100)
+            .map(|ending| gap_stats(format!("{:02}", ending), &ending_values, ending, 0.01))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let positions = (0..width)
+        .map(|pos| {
+            let column: Vec<usize> = rows.iter().map(|row| row[pos] as usize).collect();
+            PositionGaps {
+                position: pos + 1,
+                digits: (0..10)
+                    .map(|digit| gap_stats(digit.to_string(), &column, digit, 0.1))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(GapReport {
+        sample_size: rows.len(),
+        endings,
+        positions,
+    })
+}
+
+fn gap_stats(value: String, draws: &[usize], target: usize, probability: f64) -> GapStats {
+    let hits: Vec<usize> = draws
+        .iter()
+        .enumerate()
+        .filter(|(_, &v)| v == target)
+        .map(|(i, _)| i)
+        .collect();
+
+    let current_gap = hits.last().map_or(draws.len(), |&last| draws.len() - 1 - last);
+    let between: Vec<usize> = hits.windows(2).map(|w| w[1] - w[0] - 1).collect();
+    let leading_gap = hits.first().copied().unwrap_or(0);
+    let longest_gap = between
+        .iter()
+        .copied()
+        .chain([leading_gap, current_gap])
+        .max()
+        .unwrap_or(0);
+    let mean_gap = (!between.is_empty())
+        .then(|| between.iter().sum::<usize>() as f64 / between.len() as f64);
+
+    GapStats {
+        value,
+        appearances: hits.len(),
+        current_gap,
+        longest_gap,
+        mean_gap,
+        current_gap_probability: (1.0 - probability).powi(current_gap as i32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_gaps_between_appearances() {
+        let stats = gap_stats("1".to_string(), &[1, 0, 0, 1, 0, 1, 0, 0, 0], 1, 0.1);
+        assert_eq!(stats.appearances, 3);
+        assert_eq!(stats.current_gap, 3);
+        assert_eq!(stats.longest_gap, 3);
+        assert_eq!(stats.mean_gap, Some(1.5));
+        assert!((stats.current_gap_probability - 0.729).abs() < 1e-12);
+    }
+
+    #[test]
+    fn leading_gap_counts_towards_the_longest() {
+        let stats = gap_stats("7".to_string(), &[0, 0, 0, 0, 7, 7], 7, 0.1);
+        assert_eq!(stats.current_gap, 0);
+        assert_eq!(stats.longest_gap, 4);
+        assert_eq!(stats.mean_gap, Some(0.0));
+        assert_eq!(stats.current_gap_probability, 1.0);
+    }
+
+    #[test]
+    fn never_seen_value_is_overdue_for_the_whole_sample() {
+        let stats = gap_stats("9".to_string(), &[1, 2, 3], 9, 0.1);
+        assert_eq!(stats.appearances, 0);
+        assert_eq!(stats.current_gap, 3);
+        assert_eq!(stats.longest_gap, 3);
+        assert_eq!(stats.mean_gap, None);
+    }
+
+    #[test]
+    fn reports_endings_only_for_wider_series() {
+        let series: Vec<String> = ["105", "210", "305"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let report = gap_analysis(&series).unwrap();
+        assert_eq!(report.endings.len(), 100);
+        assert_eq!(report.endings[5].value, "05");
+        assert_eq!(report.endings[5].appearances, 2);
+        assert_eq!(report.positions.len(), 3);
+
+        let single: Vec<String> = vec!["5".to_string()];
+        assert!(gap_analysis(&single).unwrap().endings.is_empty());
+        assert!(gap_analysis(&[]).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use serde::Serialize;
+use statrs::distribution::{Binomial, DiscreteCDF};
+
+use super::digit_rows;
+use crate::i18n::Message;
+
+/// Window sizes used when the request does not specify any; `None` means all draws.
+pub const DEFAULT_WINDOWS: [Option<usize>; 4] = [Some(10), Some(24), Some(50), None];
+
+/// A count is hot (cold) when a count at least that high (low) has an exact
+/// binomial probability below `ALPHA` under a uniform draw.
+const ALPHA: f64 = 0.05;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Temperature {
+    Hot,
+    Neutral,
+    Cold,
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Trend {
+    Rising,
+    Steady,
+    Falling,
+}
+
+#[derive(Serialize)]
+pub struct WindowEntry {
+    pub value: String,
+    pub count: usize,
+    pub expected: f64,
+    pub temperature: Temperature,
+    /// Share in this window compared with the next larger window; `None` for the largest.
+    pub trend: Option<Trend>,
+}
+
+#[derive(Serialize)]
+pub struct WindowResult {
+    /// Requested window size; `None` for the whole history.
+    pub window: Option<usize>,
+    /// Number of draws actually in the window.
+    pub draws: usize,
+    pub endings: Vec<WindowEntry>,
+    pub digits: Vec<WindowEntry>,
+}
+
+#[derive(Serialize)]
+pub struct HotColdReport {
+    pub sample_size: usize,
+    pub windows: Vec<WindowResult>,
+}
+
+/// Frequency of each last-2 ending and each digit over the most recent `N` draws,
+/// for every requested `N`, of a chronologically ordered series (oldest first).
+pub fn hot_cold_analysis(
+    series: &[String],
+    windows: &[Option<usize>],
+) -> Result<HotColdReport, Message> {
+    if series.is_empty() {
+        return Err(Message::InsufficientAnalysisData {
+            required: 1,
+            found: 0,
+        });
+    }
+    let width = series[0].len();
+    let rows = digit_rows(series);
+
+    let mut sizes: Vec<Option<usize>> = windows.to_vec();
+    sizes.sort_by_key(|w| w.unwrap_or(usize::MAX));
+    sizes.dedup();
+
+    let mut results: Vec<WindowResult> = sizes
+        .iter()
+        .map(|&window| {
+            let draws = window.unwrap_or(rows.len()).min(rows.len());
+            let recent = &rows[rows.len() - draws..];
+            let endings = if width >= 2 {
+                let mut counts = [0usize; 100];
+                for row in recent {
+                    counts[row[width - 2] as usize * 10 + row[width - 1] as usize] += 1;
+                }
+                classify(&counts, draws, |i| format!("{:02}", i))
+            } else {
+                Vec::new()
+            };
+            let mut digit_counts = [0usize; 10];
+            for &digit in recent.iter().flatten() {
+                digit_counts[digit as usize] += 1;
+            }
+            WindowResult {
+                window,
+                draws,
+                endings,
+                digits: classify(&digit_counts, draws * width, |i| i.to_string()),
+            }
+        })
+        .collect();
+
+    // Trend compares each window with the next larger one.
+    for i in 0..results.len().saturating_sub(1) {
+        let (smaller, larger) = results.split_at_mut(i + 1);
+        let (smaller, larger) = (&mut smaller[i], &larger[0]);
+        set_trends(
+            &mut smaller.endings,
+            smaller.draws,
+            &larger.endings,
+            larger.draws,
+        );
+        set_trends(
+            &mut smaller.digits,
+            smaller.draws * width,
+            &larger.digits,
+            larger.draws * width,
+        );
+    }
+
+    Ok(HotColdReport {
+        sample_size: rows.len(),
+        windows: results,
+    })
+}
+
+fn classify(counts: &[usize], trials: usize, label: impl Fn(usize) -> String) -> Vec<WindowEntry> {
+    let p = 1.0 / counts.len() as f64;
+    let expected = trials as f64 * p;
+    let binomial = Binomial::new(p, trials as u64).unwrap();
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let count_u64 = count as u64;
+            // P(X ≥ count) and P(X ≤ count); a single hit in a short window is not rare.
+            let upper = if count == 0 {
+                1.0
+            } else {
+                binomial.sf(count_u64 - 1)
+            };
+            let lower = binomial.cdf(count_u64);
+            let temperature = if upper < ALPHA {
+                Temperature::Hot
+            } else if lower < ALPHA {
+                Temperature::Cold
+            } else {
+                Temperature::Neutral
+            };
+            WindowEntry {
+                value: label(i),
+                count,
+                expected,
+                temperature,
+                trend: None,
+            }
+        })
+        .collect()
+}
+
+fn set_trends(
+    entries: &mut [WindowEntry],
+    trials: usize,
+    reference: &[WindowEntry],
+    reference_trials: usize,
+) {
+    if trials == 0 || reference_trials == 0 {
+        return;
+    }
+    let p = 1.0 / entries.len().max(1) as f64;
+    // Treat share changes smaller than half a standard error of the window as steady.
+    let tolerance = 0.5 * (p * (1.0 - p) / trials as f64).sqrt();
+    for (entry, reference) in entries.iter_mut().zip(reference) {
+        let share = entry.count as f64 / trials as f64;
+        let reference_share = reference.count as f64 / reference_trials as f64;
+        entry.trend = Some(if share - reference_share > tolerance {
+            Trend::Rising
+        } else if reference_share - share > tolerance {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_hit_in_short_window_is_neutral() {
+        let mut counts = [0usize; 100];
+        counts[7] = 1;
+        let entries = classify(&counts, 10, |i| format!("{:02}", i));
+        assert!(entries
+            .iter()
+            .all(|entry| entry.temperature == Temperature::Neutral));
+    }
+
+    #[test]
+    fn classifies_by_exact_binomial_tail() {
+        // 100 digits with expectation 10 each: 20 is rare, 0 is rare, 12 is not.
+        let mut counts = [10usize; 10];
+        counts[0] = 20;
+        counts[1] = 0;
+        counts[2] = 12;
+        let entries = classify(&counts, 100, |i| i.to_string());
+        assert_eq!(entries[0].temperature, Temperature::Hot);
+        assert_eq!(entries[1].temperature, Temperature::Cold);
+        assert_eq!(entries[2].temperature, Temperature::Neutral);
+        assert_eq!(entries[3].expected, 10.0);
+    }
+
+    #[test]
+    fn empty_window_is_neutral() {
+        let entries = classify(&[0usize; 10], 0, |i| i.to_string());
+        assert!(entries
+            .iter()
+            .all(|entry| entry.temperature == Temperature::Neutral));
+    }
+
+    #[test]
+    fn windows_are_sorted_and_trended() {
+        let series: Vec<String> = (0..30).map(|i| format!("{:02}", i % 7)).collect();
+        let report = hot_cold_analysis(&series, &[None, Some(10), Some(10)]).unwrap();
+        assert_eq!(report.sample_size, 30);
+        let draws: Vec<usize> = report.windows.iter().map(|w| w.draws).collect();
+        assert_eq!(draws, vec![10, 30]);
+        assert!(report.windows[0].endings.iter().all(|e| e.trend.is_some()));
+        assert!(report.windows[1].endings.iter().all(|e| e.trend.is_none()));
+    }
+
+    #[test]
+    fn rejects_empty_series() {
+        assert!(hot_cold_analysis(&[], &DEFAULT_WINDOWS).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use serde::Serialize;
+
+use super::{digit_last_seen, digit_rows, top_combinations, DigitColumn};
+use crate::i18n::Message;
+
+/// Pseudo-count added to every transition (Laplace smoothing).
+pub const LAPLACE_ALPHA: f64 = 1.0;
+
+const PREDICTION_COUNT: usize = 5;
+
+#[derive(Serialize)]
+pub struct TransitionRow {
+    pub from: u8,
+    /// Observed transitions out of `from`.
+    pub total: usize,
+    /// Smoothed P(next = j | current = from), indexed by digit j.
+    pub probabilities: Vec<f64>,
+}
+
+#[derive(Serialize)]
+pub struct PositionTransitions {
+    pub position: usize,
+    pub matrix: Vec<TransitionRow>,
+    /// Digit at this position in the latest draw.
+    pub last_digit: u8,
+    pub most_likely_next: u8,
+}
+
+#[derive(Serialize)]
+pub struct MarkovPrediction {
+    pub number: String,
+    pub probability: f64,
+}
+
+#[derive(Serialize)]
+pub struct MarkovReport {
+    pub sample_size: usize,
+    pub smoothing: f64,
+    pub positions: Vec<PositionTransitions>,
+    /// Most likely next numbers, treating positions as independent chains.
+    pub predictions: Vec<MarkovPrediction>,
+    /// Tens and units of the top prediction; `None` for single-digit series.
+    pub predicted_ending: Option<String>,
+}
+
+/// First-order Markov model per digit position, estimated from a chronologically
+/// ordered series (oldest first) and conditioned on the latest draw.
+pub fn markov_analysis(series: &[String]) -> Result<MarkovReport, Message> {
+    if series.len() < 2 {
+        return Err(Message::InsufficientAnalysisData {
+            required: 2,
+            found: series.len(),
+        });
+    }
+    let width = series[0].len();
+    let rows = digit_rows(series);
+    let latest = &rows[rows.len() - 1];
+
+    let positions: Vec<PositionTransitions> = (0..width)
+        .map(|pos| {
+            let mut counts = [[0usize; 10]; 10];
+            for pair in rows.windows(2) {
+                counts[pair[0][pos] as usize][pair[1][pos] as usize] += 1;
+            }
+            let matrix: Vec<TransitionRow> = counts
+                .iter()
+                .enumerate()
+                .map(|(from, row)| {
+                    let total: usize = row.iter().sum();
+                    let denominator = total as f64 + 10.0 * LAPLACE_ALPHA;
+                    TransitionRow {
+                        from: from as u8,
+                        total,
+                        probabilities: row
+                            .iter()
+                            .map(|&c| (c as f64 + LAPLACE_ALPHA) / denominator)
+                            .collect(),
+                    }
+                })
+                .collect();
+            let last_digit = latest[pos];
+            let most_likely_next = argmax(&matrix[last_digit as usize].probabilities);
+            PositionTransitions {
+                position: pos + 1,
+                matrix,
+                last_digit,
+                most_likely_next,
+            }
+        })
+        .collect();
+
+    let predictions = top_predictions(&positions, &rows);
+    let predicted_ending = predictions
+        .first()
+        .filter(|_| width >= 2)
+        .map(|p| p.number[width - 2..].to_string());
+
+    Ok(MarkovReport {
+        sample_size: rows.len(),
+        smoothing: LAPLACE_ALPHA,
+        positions,
+        predictions,
+        predicted_ending,
+    })
+}
+
+/// Lowest digit wins ties.
+fn argmax(probabilities: &[f64]) -> u8 {
+    let mut best = 0;
+    for (digit, &p) in probabilities.iter().enumerate() {
+        if p > probabilities[best] {
+            best = digit;
+        }
+    }
+    best as u8
+}
+
+fn top_predictions(positions: &[PositionTransitions], rows: &[Vec<u8>]) -> Vec<MarkovPrediction> {
+    let columns: Vec<DigitColumn> = positions
+        .iter()
+        .enumerate()
+        .map(|(pos, column)| DigitColumn {
+            probabilities: &column.matrix[column.last_digit as usize].probabilities,
+            last_seen: digit_last_seen(rows, pos),
+        })
+        .collect();
+    top_combinations(&columns, PREDICTION_COUNT)
+        .into_iter()
+        .map(|(number, probability)| MarkovPrediction {
+            number,
+            probability,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn smooths_transition_counts() {
+        let report = markov_analysis(&series(&["12", "23", "12", "23", "12"])).unwrap();
+        let first = &report.positions[0];
+        assert_eq!(first.last_digit, 1);
+        assert_eq!(first.most_likely_next, 2);
+        let row = &first.matrix[1];
+        assert_eq!(row.total, 2);
+        assert!((row.probabilities[2] - 3.0 / 12.0).abs() < 1e-12);
+        assert!((row.probabilities[5] - 1.0 / 12.0).abs() < 1e-12);
+        // A digit never seen at this position falls back to uniform.
+        assert_eq!(first.matrix[9].total, 0);
+        assert!(first.matrix[9]
+            .probabilities
+            .iter()
+            .all(|&p| (p - 0.1).abs() < 1e-12));
+        for row in &first.matrix {
+            assert!((row.probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn predicts_from_the_latest_draw() {
+        let report = markov_analysis(&series(&["12", "23", "12", "23", "12"])).unwrap();
+        assert_eq!(report.predictions.len(), PREDICTION_COUNT);
+        assert_eq!(report.predictions[0].number, "23");
+        assert!((report.predictions[0].probability - 0.0625).abs() < 1e-12);
+        assert_eq!(report.predicted_ending.as_deref(), Some("23"));
+    }
+
+    #[test]
+    fn single_digit_series_has_no_ending() {
+        let report = markov_analysis(&series(&["1", "2", "1"])).unwrap();
+        assert_eq!(report.predicted_ending, None);
+    }
+
+    #[test]
+    fn argmax_prefers_the_lowest_digit_on_ties() {
+        assert_eq!(argmax(&[0.2, 0.3, 0.3, 0.2]), 1);
+        assert_eq!(argmax(&[0.1; 10]), 0);
+    }
+
+    #[test]
+    fn needs_two_draws() {
+        assert!(matches!(
+            markov_analysis(&series(&["12"])).err(),
+            Some(Message::InsufficientAnalysisData {
+                required: 2,
+                found: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+pub mod bayes;
+pub mod calendar;
+pub mod cooccurrence;
+pub mod descriptive;
+pub mod gaps;
+pub mod hot_cold;
+pub mod markov;
+pub mod monte_carlo;
+pub mod permutations;
+pub mod positional;
+pub mod randomness;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// A distinct value of a series with how often and how recently it occurred.
+pub struct FrequencyRank<'a> {
+    pub value: &'a str,
+    pub count: usize,
+    /// Index of its latest occurrence; series are oldest first, so larger is newer.
+    pub last_seen: usize,
+}
+
+/// The ordering every frequency ranking uses, best first: more occurrences, then the
+/// more recent last occurrence, then the smaller value.
+pub fn frequency_order<T: Ord>(
+    a: (usize, Option<usize>, T),
+    b: (usize, Option<usize>, T),
+) -> Ordering {
+    b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2))
+}
+
+/// Distinct values of `series` ranked by [`frequency_order`].
+pub fn rank_by_frequency<'a>(series: &[&'a str]) -> Vec<FrequencyRank<'a>> {
+    let mut ranks: BTreeMap<&str, FrequencyRank> = BTreeMap::new();
+    for (i, &value) in series.iter().enumerate() {
+        let rank = ranks.entry(value).or_insert(FrequencyRank {
+            value,
+            count: 0,
+            last_seen: i,
+        });
+        rank.count += 1;
+        rank.last_seen = i;
+    }
+    let mut ranks: Vec<FrequencyRank> = ranks.into_values().collect();
+    // Equal-width digit strings order the same as their numeric values.
+    ranks.sort_by(|a, b| {
+        frequency_order(
+            (a.count, Some(a.last_seen), a.value),
+            (b.count, Some(b.last_seen), b.value),
+        )
+    });
+    ranks
+}
+
+/// Splits each number into its digits (0-9), one row per number. The series analyses
+/// expect input cleaned by [`crate::validation::validate_numbers`]: digit strings of
+/// one width, oldest first.
+pub fn digit_rows(series: &[String]) -> Vec<Vec<u8>> {
+    series
+        .iter()
+        .map(|s| s.bytes().map(|b| b - b'0').collect())
+        .collect()
+}
+
+/// One digit position of the numbers built by [`top_combinations`].
+pub struct DigitColumn<'a> {
+    /// Probability of each digit 0-9 at this position.
+    pub probabilities: &'a [f64],
+    /// Index of the latest draw with each digit at this position; see [`digit_last_seen`].
+    pub last_seen: [Option<usize>; 10],
+}
+
+/// Index of the latest row with each digit at `pos`; rows are oldest first.
+pub fn digit_last_seen(rows: &[Vec<u8>], pos: usize) -> [Option<usize>; 10] {
+    let mut last_seen = [None; 10];
+    for (i, row) in rows.iter().enumerate() {
+        last_seen[row[pos] as usize] = Some(i);
+    }
+    last_seen
+}
+
+/// The `k` most probable numbers formed by picking one digit per position, where
+/// positions are independent. Equal probabilities fall back to [`frequency_order`],
+/// taking a number as last seen when the stalest of its digits was.
+pub fn top_combinations(columns: &[DigitColumn], k: usize) -> Vec<(String, f64)> {
+    let mut beam: Vec<(String, f64, Option<usize>)> = vec![(String::new(), 1.0, None)];
+    for (pos, column) in columns.iter().enumerate() {
+        let mut candidates: Vec<(String, f64, Option<usize>)> = beam
+            .iter()
+            .flat_map(|(prefix, p, seen)| {
+                column
+                    .probabilities
+                    .iter()
+                    .zip(column.last_seen)
+                    .enumerate()
+                    .map(move |(digit, (q, digit_seen))| {
+                        let seen = if pos == 0 {
+                            digit_seen
+                        } else {
+                            (*seen).min(digit_seen)
+                        };
+                        (format!("{}{}", prefix, digit), p * q, seen)
+                    })
+            })
+            .collect();
+        // Keeping the best `k` prefixes is exact because positions are independent.
+        candidates.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then_with(|| frequency_order((0, a.2, &a.0), (0, b.2, &b.0)))
+        });
+        candidates.truncate(k);
+        beam = candidates;
+    }
+    beam.into_iter().map(|(number, p, _)| (number, p)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency_ranking_prefers_recent_then_smaller_values() {
+        let series = ["07", "03", "07", "03", "11", "05"];
+        let ranked: Vec<&str> = rank_by_frequency(&series).iter().map(|r| r.value).collect();
+        // "03" and "07" tie on count; "03" appeared last. "05" and "11" tie on count too.
+        assert_eq!(ranked, ["03", "07", "05", "11"]);
+    }
+
+    #[test]
+    fn frequency_order_puts_never_seen_last() {
+        assert_eq!(
+            frequency_order((1, Some(0), 5), (1, None, 0)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn top_combinations_multiplies_position_probabilities() {
+        let first = [0.0, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.5];
+        let second = [0.2, 0.0, 0.0, 0.8, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let columns = [
+            DigitColumn {
+                probabilities: &first,
+                last_seen: [Some(0); 10],
+            },
+            DigitColumn {
+                probabilities: &second,
+                last_seen: [Some(0); 10],
+            },
+        ];
+        let top = top_combinations(&columns, 3);
+        let numbers: Vec<&str> = top.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(numbers, ["13", "93", "10"]);
+        assert!((top[0].1 - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn top_combinations_breaks_ties_by_recency() {
+        let uniform = [0.1; 10];
+        let rows = vec![vec![7, 2], vec![3, 9], vec![7, 1]];
+        let columns: Vec<DigitColumn> = (0..2)
+            .map(|pos| DigitColumn {
+                probabilities: &uniform,
+                last_seen: digit_last_seen(&rows, pos),
+            })
+            .collect();
+        let numbers: Vec<String> = top_combinations(&columns, 4)
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        // Every number is equally likely, so the one whose stalest digit is newest wins;
+        // equally recent ones go to the smaller number.
+        assert_eq!(numbers, ["71", "31", "39", "79"]);
+    }
+}
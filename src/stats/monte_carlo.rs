@@ -0,0 +1,225 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::descriptive::percentile;
+use super::digit_rows;
+use crate::i18n::Message;
+
+pub const DEFAULT_SIMULATIONS: usize = 1000;
+pub const MAX_SIMULATIONS: usize = 20_000;
+/// Upper bound on simulations × sample size; longer series get fewer simulations.
+pub const MAX_SIMULATED_DRAWS: usize = 2_000_000;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedStatistic {
+    /// Occurrences of the most frequent exact number.
+    MaxFrequency,
+    /// Longest run of draws between two appearances of the same two-digit ending
+    /// (or digit, for 1-digit series).
+    LongestGap,
+    /// Share of all drawn digits taken by the most common digit.
+    TopDigitShare,
+}
+
+pub const ALL_STATISTICS: [SimulatedStatistic; 3] = [
+    SimulatedStatistic::MaxFrequency,
+    SimulatedStatistic::LongestGap,
+    SimulatedStatistic::TopDigitShare,
+];
+
+#[derive(Serialize)]
+pub struct SimulationResult {
+    pub statistic: SimulatedStatistic,
+    pub observed: f64,
+    pub simulated_mean: f64,
+    pub simulated_p5: f64,
+    pub simulated_median: f64,
+    pub simulated_p95: f64,
+    /// Share of simulated histories with a value at least as extreme as `observed`.
+    pub p_value: f64,
+    /// Percentage of simulated histories with a strictly smaller value.
+    pub percentile_rank: f64,
+}
+
+#[derive(Serialize)]
+pub struct MonteCarloReport {
+    pub sample_size: usize,
+    pub digit_width: usize,
+    pub simulations: usize,
+    pub seed: Option<u64>,
+    pub results: Vec<SimulationResult>,
+}
+
+/// Compares statistics of the real series against `simulations` uniformly random
+/// histories of the same length and digit width. CPU-bound; run it off the async runtime.
+pub fn monte_carlo_analysis(
+    series: &[String],
+    statistics: &[SimulatedStatistic],
+    simulations: usize,
+    seed: Option<u64>,
+) -> Result<MonteCarloReport, Message> {
+    if series.len() < 10 {
+        return Err(Message::InsufficientAnalysisData {
+            required: 10,
+            found: series.len(),
+        });
+    }
+    let simulations = simulations
+        .clamp(1, MAX_SIMULATIONS)
+        .min(MAX_SIMULATED_DRAWS / series.len())
+        .max(1);
+    let width = series[0].len();
+    let observed_rows = digit_rows(series);
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let mut simulated: Vec<Vec<f64>> = vec![Vec::with_capacity(simulations); statistics.len()];
+    let mut rows = vec![vec![0u8; width]; observed_rows.len()];
+    for _ in 0..simulations {
+        for digit in rows.iter_mut().flatten() {
+            *digit = rng.gen_range(0..10);
+        }
+        for (values, &statistic) in simulated.iter_mut().zip(statistics) {
+            values.push(compute(statistic, &rows));
+        }
+    }
+
+    let results = statistics
+        .iter()
+        .zip(simulated)
+        .map(|(&statistic, mut values)| {
+            let observed = compute(statistic, &observed_rows);
+            values.sort_by(|a, b| a.total_cmp(b));
+            let n = values.len() as f64;
+            SimulationResult {
+                statistic,
+                observed,
+                simulated_mean: values.iter().sum::<f64>() / n,
+                simulated_p5: percentile(&values, 5.0),
+                simulated_median: percentile(&values, 50.0),
+                simulated_p95: percentile(&values, 95.0),
+                p_value: values.iter().filter(|&&v| v >= observed).count() as f64 / n,
+                percentile_rank: values.iter().filter(|&&v| v < observed).count() as f64 / n
+                    * 100.0,
+            }
+        })
+        .collect();
+
+    Ok(MonteCarloReport {
+        sample_size: observed_rows.len(),
+        digit_width: width,
+        simulations,
+        seed,
+        results,
+    })
+}
+
+fn compute(statistic: SimulatedStatistic, rows: &[Vec<u8>]) -> f64 {
+    match statistic {
+        SimulatedStatistic::MaxFrequency => {
+            let mut counts: HashMap<&[u8], usize> = HashMap::new();
+            for row in rows {
+                *counts.entry(row.as_slice()).or_default() += 1;
+            }
+            counts.values().copied().max().unwrap_or(0) as f64
+        }
+        SimulatedStatistic::LongestGap => {
+            let width = rows.first().map_or(0, Vec::len);
+            let (values, categories): (Vec<usize>, usize) = if width >= 2 {
+                let endings = rows
+                    .iter()
+                    .map(|row| row[width - 2] as usize * 10 + row[width - 1] as usize)
+                    .collect();
+                (endings, 100)
+            } else {
+                (rows.iter().map(|row| row[0] as usize).collect(), 10)
+            };
+            longest_gap(&values, categories) as f64
+        }
+        SimulatedStatistic::TopDigitShare => {
+            let mut counts = [0usize; 10];
+            for &digit in rows.iter().flatten() {
+                counts[digit as usize] += 1;
+            }
+            let total: usize = counts.iter().sum();
+            counts.iter().copied().max().unwrap_or(0) as f64 / total.max(1) as f64
+        }
+    }
+}
+
+/// Longest run of draws between two appearances of the same category. Unlike the
+/// overdue analysis, spells before the first or after the last appearance are left
+/// out; with 100 endings some almost always never appear, which would pin the statistic
+/// to the sample size.
+fn longest_gap(values: &[usize], categories: usize) -> usize {
+    let mut last_seen: Vec<Option<usize>> = vec![None; categories];
+    let mut longest = 0;
+    for (i, &value) in values.iter().enumerate() {
+        if let Some(last) = last_seen[value] {
+            longest = longest.max(i - last - 1);
+        }
+        last_seen[value] = Some(i);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("{:02}", i * 37 % 100)).collect()
+    }
+
+    #[test]
+    fn rejects_short_series() {
+        assert!(monte_carlo_analysis(&series(9), &ALL_STATISTICS, 10, Some(1)).is_err());
+    }
+
+    #[test]
+    fn same_seed_gives_same_results() {
+        let run = || monte_carlo_analysis(&series(40), &ALL_STATISTICS, 200, Some(7)).unwrap();
+        let (a, b) = (run(), run());
+        for (x, y) in a.results.iter().zip(&b.results) {
+            assert_eq!(x.simulated_mean, y.simulated_mean);
+            assert_eq!(x.p_value, y.p_value);
+        }
+        assert_eq!(a.results.len(), ALL_STATISTICS.len());
+    }
+
+    #[test]
+    fn simulations_are_capped_by_total_draws() {
+        let numbers = series(MAX_SIMULATED_DRAWS / 100);
+        let report = monte_carlo_analysis(
+            &numbers,
+            &[SimulatedStatistic::MaxFrequency],
+            MAX_SIMULATIONS,
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(report.simulations, 100);
+        let report =
+            monte_carlo_analysis(&series(20), &ALL_STATISTICS, usize::MAX, Some(1)).unwrap();
+        assert_eq!(report.simulations, MAX_SIMULATIONS);
+    }
+
+    #[test]
+    fn longest_gap_ignores_open_spells() {
+        // 3 repeats after a gap of 2; 5 and 9 never repeat.
+        assert_eq!(longest_gap(&[5, 3, 1, 2, 3, 9], 10), 2);
+        assert_eq!(longest_gap(&[1, 2, 3], 10), 0);
+    }
+
+    #[test]
+    fn observed_statistics_match_the_series() {
+        let rows = vec![vec![1, 2], vec![1, 2], vec![3, 4]];
+        assert_eq!(compute(SimulatedStatistic::MaxFrequency, &rows), 2.0);
+        assert_eq!(compute(SimulatedStatistic::LongestGap, &rows), 0.0);
+        assert!((compute(SimulatedStatistic::TopDigitShare, &rows) - 2.0 / 6.0).abs() < 1e-12);
+    }
+}
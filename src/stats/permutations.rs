@@ -0,0 +1,179 @@
+use serde::Serialize;
+
+use super::bayes::bayes_analysis;
+use crate::i18n::Message;
+
+#[derive(Serialize)]
+pub struct RankedPermutation {
+    pub number: String,
+    /// Times this exact number appears in the series.
+    pub historical_count: usize,
+    pub historical_frequency: f64,
+    /// Posterior predictive probability from the Bayesian model.
+    pub model_probability: f64,
+}
+
+#[derive(Serialize)]
+pub struct PermutationReport {
+    pub prediction: String,
+    pub sample_size: usize,
+    /// Every distinct ordering of the prediction's digits, best first.
+    pub permutations: Vec<RankedPermutation>,
+    /// Summed model probability of all permutations, i.e. of winning with the full set.
+    pub combined_probability: f64,
+}
+
+/// Every distinct ordering of the digits of `number`, in ascending order.
+pub fn unique_permutations(number: &str) -> Vec<String> {
+    let mut digits: Vec<char> = number.chars().collect();
+    digits.sort_unstable();
+    let mut permutations = vec![digits.iter().collect::<String>()];
+    // Standard next-permutation walk; skips duplicates when digits repeat.
+    loop {
+        let Some(i) = (1..digits.len()).rev().find(|&i| digits[i - 1] < digits[i]) else {
+            return permutations;
+        };
+        let j = (i..digits.len())
+            .rev()
+            .find(|&j| digits[j] > digits[i - 1])
+            .unwrap();
+        digits.swap(i - 1, j);
+        digits[i..].reverse();
+        permutations.push(digits.iter().collect());
+    }
+}
+
+/// Expands `prediction` (the model's top number when `None`) into all of its digit
+/// permutations ("กลับเลข"), ranked by model probability, then historical count.
+pub fn permutation_ranking(
+    series: &[String],
+    prediction: Option<&str>,
+) -> Result<PermutationReport, Message> {
+    if series.is_empty() {
+        return Err(Message::InsufficientAnalysisData {
+            required: 1,
+            found: 0,
+        });
+    }
+    let width = series[0].len();
+    if ![2, 3, 6].contains(&width) {
+        return Err(Message::PermutationWidthUnsupported);
+    }
+    let bayes = bayes_analysis(series)?;
+    let prediction = match prediction.map(str::trim) {
+        Some(p) if p.len() == width && p.chars().all(|c| c.is_ascii_digit()) => p.to_string(),
+        Some(p) => {
+            return Err(Message::InvalidNumber {
+                number: p.to_string(),
+                width,
+            })
+        }
+        None => bayes.predictions[0].number.clone(),
+    };
+
+    let model_probability = |number: &str| match &bayes.endings {
+        Some(endings) if width == 2 => {
+            endings.categories[number.parse::<usize>().unwrap()].posterior_mean
+        }
+        _ => number
+            .bytes()
+            .zip(&bayes.positions)
+            .map(|(b, p)| p.posterior.categories[(b - b'0') as usize].posterior_mean)
+            .product(),
+    };
+    let mut permutations: Vec<RankedPermutation> = unique_permutations(&prediction)
+        .into_iter()
+        .map(|number| {
+            let historical_count = series.iter().filter(|&n| *n == number).count();
+            RankedPermutation {
+                historical_frequency: historical_count as f64 / series.len() as f64,
+                historical_count,
+                model_probability: model_probability(&number),
+                number,
+            }
+        })
+        .collect();
+    permutations.sort_by(|a, b| {
+        b.model_probability
+            .total_cmp(&a.model_probability)
+            .then(b.historical_count.cmp(&a.historical_count))
+            .then(a.number.cmp(&b.number))
+    });
+
+    Ok(PermutationReport {
+        prediction,
+        sample_size: series.len(),
+        combined_probability: permutations.iter().map(|p| p.model_probability).sum(),
+        permutations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn lists_distinct_orderings_in_ascending_order() {
+        assert_eq!(
+            unique_permutations("312"),
+            vec!["123", "132", "213", "231", "312", "321"]
+        );
+        assert_eq!(unique_permutations("121"), vec!["112", "121", "211"]);
+        assert_eq!(unique_permutations("777"), vec!["777"]);
+        assert_eq!(unique_permutations("123456").len(), 720);
+        assert_eq!(unique_permutations("112233").len(), 90);
+    }
+
+    #[test]
+    fn ranks_permutations_by_model_probability() {
+        let report = permutation_ranking(&series(&["12", "21", "12"]), None).unwrap();
+        assert_eq!(report.prediction, "12");
+        let numbers: Vec<&str> = report
+            .permutations
+            .iter()
+            .map(|p| p.number.as_str())
+            .collect();
+        assert_eq!(numbers, ["12", "21"]);
+        assert_eq!(report.permutations[0].historical_count, 2);
+        assert!((report.permutations[0].model_probability - 3.0 / 103.0).abs() < 1e-12);
+        assert!((report.combined_probability - 5.0 / 103.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wider_numbers_use_the_position_posteriors() {
+        let report = permutation_ranking(&series(&["123", "321"]), Some("213")).unwrap();
+        assert_eq!(report.prediction, "213");
+        assert_eq!(report.permutations.len(), 6);
+        let total: f64 = report
+            .permutations
+            .iter()
+            .map(|p| p.model_probability)
+            .sum();
+        assert!((report.combined_probability - total).abs() < 1e-12);
+        assert!(report
+            .permutations
+            .windows(2)
+            .all(|w| w[0].model_probability >= w[1].model_probability));
+    }
+
+    #[test]
+    fn rejects_unsupported_input() {
+        assert!(matches!(
+            permutation_ranking(&series(&["1234"]), None).err(),
+            Some(Message::PermutationWidthUnsupported)
+        ));
+        assert!(matches!(
+            permutation_ranking(&series(&["12"]), Some("1x")).err(),
+            Some(Message::InvalidNumber { width: 2, .. })
+        ));
+        assert!(matches!(
+            permutation_ranking(&series(&["12"]), Some("123")).err(),
+            Some(Message::InvalidNumber { .. })
+        ));
+        assert!(permutation_ranking(&[], None).is_err());
+    }
+}
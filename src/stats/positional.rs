@@ -0,0 +1,149 @@
+use serde::Serialize;
+
+use super::{digit_last_seen, digit_rows, frequency_order};
+use crate::i18n::Message;
+
+#[derive(Serialize)]
+pub struct DigitCell {
+    pub digit: u8,
+    pub count: usize,
+    /// Index of the latest draw with this digit here; series are oldest first.
+    pub last_seen: Option<usize>,
+    pub percentage: f64,
+    pub expected: f64,
+    /// Binomial z-score of `count` against the uniform expectation (p = 0.1).
+    pub z_score: f64,
+}
+
+#[derive(Serialize)]
+pub struct PositionColumn {
+    /// 1-based digit position, left to right.
+    pub position: usize,
+    /// Always ten cells, ordered by digit 0-9.
+    pub cells: Vec<DigitCell>,
+}
+
+#[derive(Serialize)]
+pub struct DigitMatrix {
+    pub sample_size: usize,
+    pub digit_width: usize,
+    pub positions: Vec<PositionColumn>,
+}
+
+impl DigitMatrix {
+    /// Most frequent digit per position; ties follow [`frequency_order`].
+    pub fn top_digits(&self) -> Vec<(usize, &DigitCell)> {
+        self.positions
+            .iter()
+            .filter_map(|column| {
+                let top = column.cells.iter().min_by(|a, b| {
+                    frequency_order(
+                        (a.count, a.last_seen, a.digit),
+                        (b.count, b.last_seen, b.digit),
+                    )
+                })?;
+                Some((column.position, top))
+            })
+            .collect()
+    }
+}
+
+/// Builds the 10×N digit-by-position frequency matrix of an equal-width series.
+pub fn digit_position_matrix(series: &[String]) -> Result<DigitMatrix, Message> {
+    if series.is_empty() {
+        return Err(Message::InsufficientAnalysisData {
+            required: 1,
+            found: 0,
+        });
+    }
+    let width = series[0].len();
+    let rows = digit_rows(series);
+
+    let n = rows.len() as f64;
+    let expected = n / 10.0;
+    let sd = (n * 0.1 * 0.9).sqrt();
+
+    let positions = (0..width)
+        .map(|pos| {
+            let mut counts = [0usize; 10];
+            for row in &rows {
+                counts[row[pos] as usize] += 1;
+            }
+            let last_seen = digit_last_seen(&rows, pos);
+            let cells = counts
+                .iter()
+                .enumerate()
+                .map(|(digit, &count)| DigitCell {
+                    digit: digit as u8,
+                    count,
+                    last_seen: last_seen[digit],
+                    percentage: count as f64 / n * 100.0,
+                    expected,
+                    z_score: (count as f64 - expected) / sd,
+                })
+                .collect();
+            PositionColumn {
+                position: pos + 1,
+                cells,
+            }
+        })
+        .collect();
+
+    Ok(DigitMatrix {
+        sample_size: rows.len(),
+        digit_width: width,
+        positions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn counts_every_digit_at_every_position() {
+        let matrix = digit_position_matrix(&series(&["12", "32", "15", "12"])).unwrap();
+        assert_eq!(matrix.sample_size, 4);
+        assert_eq!(matrix.digit_width, 2);
+        assert_eq!(matrix.positions.len(), 2);
+        let first = &matrix.positions[0];
+        assert_eq!(first.position, 1);
+        assert_eq!(first.cells.len(), 10);
+        assert_eq!(first.cells[1].count, 3);
+        assert_eq!(first.cells[1].last_seen, Some(3));
+        assert_eq!(first.cells[3].count, 1);
+        assert_eq!(first.cells[0].last_seen, None);
+        assert!((first.cells[1].percentage - 75.0).abs() < 1e-9);
+        assert!((first.cells[1].expected - 0.4).abs() < 1e-9);
+        // (3 - 0.4) / sqrt(4 · 0.1 · 0.9)
+        assert!((first.cells[1].z_score - 2.6 / 0.6).abs() < 1e-9);
+        let total: usize = matrix.positions[1].cells.iter().map(|c| c.count).sum();
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn top_digit_ties_go_to_the_most_recent() {
+        let matrix = digit_position_matrix(&series(&["13", "31"])).unwrap();
+        let top: Vec<(usize, u8)> = matrix
+            .top_digits()
+            .into_iter()
+            .map(|(position, cell)| (position, cell.digit))
+            .collect();
+        assert_eq!(top, vec![(1, 3), (2, 1)]);
+    }
+
+    #[test]
+    fn rejects_an_empty_series() {
+        assert!(matches!(
+            digit_position_matrix(&[]).err(),
+            Some(Message::InsufficientAnalysisData {
+                required: 1,
+                found: 0
+            })
+        ));
+    }
+}
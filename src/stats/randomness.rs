@@ -0,0 +1,278 @@
+use serde::Serialize;
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
+
+use super::digit_rows;
+use crate::i18n::Message;
+
+/// Significance level used for the pass/fail verdict of every test.
+pub const ALPHA: f64 = 0.05;
+
+#[derive(Serialize)]
+pub struct RandomnessTest {
+    pub name: String,
+    pub statistic: f64,
+    /// `None` when the test is not applicable to the data (e.g. a constant series).
+    pub p_value: Option<f64>,
+    pub passed: Option<bool>,
+}
+
+impl RandomnessTest {
+    fn new(name: String, statistic: f64, p_value: Option<f64>) -> Self {
+        RandomnessTest {
+            name,
+            statistic,
+            p_value,
+            passed: p_value.map(|p| p >= ALPHA),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RandomnessReport {
+    pub sample_size: usize,
+    pub digit_width: usize,
+    pub alpha: f64,
+    pub tests: Vec<RandomnessTest>,
+}
+
+/// Runs the uniformity and independence tests on a chronologically ordered series
+/// of equal-width lottery numbers (oldest first).
+pub fn run_randomness_suite(series: &[String]) -> Result<RandomnessReport, Message> {
+    if series.len() < 10 {
+        return Err(Message::InsufficientAnalysisData {
+            required: 10,
+            found: series.len(),
+        });
+    }
+    let width = series[0].len();
+    let values: Vec<f64> = series.iter().filter_map(|s| s.parse::<f64>().ok()).collect();
+
+    let rows = digit_rows(series);
+    let mut tests: Vec<RandomnessTest> = (0..width)
+        .map(|pos| chi_square_position(&rows, pos))
+        .collect();
+    tests.push(kolmogorov_smirnov(&values, 10f64.powi(width as i32)));
+    tests.push(runs_test(&values));
+    tests.push(serial_correlation(&values));
+
+    Ok(RandomnessReport {
+        sample_size: series.len(),
+        digit_width: width,
+        alpha: ALPHA,
+        tests,
+    })
+}
+
+/// Chi-square goodness-of-fit of the digits at `pos` against a uniform 0-9 distribution.
+fn chi_square_position(rows: &[Vec<u8>], pos: usize) -> RandomnessTest {
+    let mut observed = [0usize; 10];
+    for row in rows {
+        observed[row[pos] as usize] += 1;
+    }
+    let expected = rows.len() as f64 / 10.0;
+    let statistic: f64 = observed
+        .iter()
+        .map(|&o| (o as f64 - expected).powi(2) / expected)
+        .sum();
+    let p_value = ChiSquared::new(9.0).unwrap().sf(statistic);
+    RandomnessTest::new(
+        format!("Chi-square uniformity (position {})", pos + 1),
+        statistic,
+        Some(p_value),
+    )
+}
+
+/// One-sample Kolmogorov–Smirnov test of the values against the discrete uniform
+/// distribution on `0..upper`.
+fn kolmogorov_smirnov(values: &[f64], upper: f64) -> RandomnessTest {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+
+    let mut d: f64 = 0.0;
+    let mut group_start = 0;
+    for i in 0..sorted.len() {
+        if i + 1 < sorted.len() && sorted[i + 1] == sorted[i] {
+            continue;
+        }
+        let x = sorted[i];
+        let after_jump = ((i + 1) as f64 / n - (x + 1.0) / upper).abs();
+        let before_jump = (group_start as f64 / n - x / upper).abs();
+        d = d.max(after_jump).max(before_jump);
+        group_start = i + 1;
+    }
+
+    let sqrt_n = n.sqrt();
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * d;
+    RandomnessTest::new(
+        "Kolmogorov–Smirnov (numeric values)".to_string(),
+        d,
+        Some(kolmogorov_sf(lambda)),
+    )
+}
+
+/// Survival function of the Kolmogorov distribution, Q(λ) = 2 Σ (-1)^(k-1) e^(-2k²λ²).
+fn kolmogorov_sf(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let sum: f64 = (1..=100u32)
+        .map(|k| {
+            let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+            sign * (-2.0 * f64::from(k * k) * lambda * lambda).exp()
+        })
+        .sum();
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Wald–Wolfowitz runs test above/below the median; values equal to the median are dropped.
+fn runs_test(values: &[f64]) -> RandomnessTest {
+    let name = "Wald–Wolfowitz runs (above/below median)".to_string();
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    };
+
+    let signs: Vec<bool> = values
+        .iter()
+        .filter(|&&v| v != median)
+        .map(|&v| v > median)
+        .collect();
+    let n1 = signs.iter().filter(|&&above| above).count() as f64;
+    let n2 = signs.len() as f64 - n1;
+    if n1 == 0.0 || n2 == 0.0 {
+        return RandomnessTest::new(name, 0.0, None);
+    }
+
+    let runs = 1 + signs.windows(2).filter(|w| w[0] != w[1]).count();
+    let n = n1 + n2;
+    let mean = 2.0 * n1 * n2 / n + 1.0;
+    let variance = 2.0 * n1 * n2 * (2.0 * n1 * n2 - n) / (n * n * (n - 1.0));
+    if variance <= 0.0 {
+        return RandomnessTest::new(name, runs as f64, None);
+    }
+    let z = (runs as f64 - mean) / variance.sqrt();
+    RandomnessTest::new(name, z, Some(two_sided_normal_p(z)))
+}
+
+/// Lag-1 serial correlation; under independence r ≈ N(-1/n, 1/n).
+fn serial_correlation(values: &[f64]) -> RandomnessTest {
+    let name = "Serial correlation (lag 1)".to_string();
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let denominator: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+    if denominator == 0.0 {
+        return RandomnessTest::new(name, 0.0, None);
+    }
+    let numerator: f64 = values
+        .windows(2)
+        .map(|w| (w[0] - mean) * (w[1] - mean))
+        .sum();
+    let r = numerator / denominator;
+    let z = (r + 1.0 / n) * n.sqrt();
+    RandomnessTest::new(name, r, Some(two_sided_normal_p(z)))
+}
+
+fn two_sided_normal_p(z: f64) -> f64 {
+    (2.0 * Normal::new(0.0, 1.0).unwrap().sf(z.abs())).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[u32], width: usize) -> Vec<String> {
+        values
+            .iter()
+            .map(|v| format!("{:0width$}", v, width = width))
+            .collect()
+    }
+
+    #[test]
+    fn chi_square_matches_reference_p_value() {
+        // Counts 4, 0 and 2 for every other digit: χ² = 4 on 9 degrees of freedom.
+        let digits = [0, 0, 0, 0, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9];
+        let rows: Vec<Vec<u8>> = digits.iter().map(|&d| vec![d]).collect();
+        let test = chi_square_position(&rows, 0);
+        assert!((test.statistic - 4.0).abs() < 1e-12);
+        assert!((test.p_value.unwrap() - 0.911_412_526_8).abs() < 1e-6);
+        assert_eq!(test.passed, Some(true));
+    }
+
+    #[test]
+    fn chi_square_fails_a_constant_digit() {
+        let rows = vec![vec![7u8]; 10];
+        let test = chi_square_position(&rows, 0);
+        assert!((test.statistic - 90.0).abs() < 1e-12);
+        assert_eq!(test.passed, Some(false));
+    }
+
+    #[test]
+    fn kolmogorov_survival_matches_reference_values() {
+        assert!((kolmogorov_sf(1.0) - 0.269_999_671_7).abs() < 1e-8);
+        assert!((kolmogorov_sf(1.36) - 0.049_485_876_8).abs() < 1e-8);
+        assert_eq!(kolmogorov_sf(0.1), 1.0);
+    }
+
+    #[test]
+    fn kolmogorov_smirnov_accepts_every_value_once() {
+        let values: Vec<f64> = (0..100).map(f64::from).collect();
+        let test = kolmogorov_smirnov(&values, 100.0);
+        assert!(test.statistic.abs() < 1e-12);
+        assert_eq!(test.p_value, Some(1.0));
+    }
+
+    #[test]
+    fn kolmogorov_smirnov_rejects_a_constant_series() {
+        let test = kolmogorov_smirnov(&[0.0; 20], 100.0);
+        assert!((test.statistic - 0.99).abs() < 1e-12);
+        assert_eq!(test.passed, Some(false));
+    }
+
+    #[test]
+    fn alternating_series_fails_runs_and_serial_correlation() {
+        let values: Vec<f64> = (0..10)
+            .map(|i| if i % 2 == 0 { 10.0 } else { 90.0 })
+            .collect();
+        let runs = runs_test(&values);
+        // 10 runs against an expected 6 with variance 20/9.
+        assert!((runs.statistic - 4.0 / (20.0f64 / 9.0).sqrt()).abs() < 1e-12);
+        assert_eq!(runs.passed, Some(false));
+        let serial = serial_correlation(&values);
+        assert!(serial.statistic < -0.8);
+        assert_eq!(serial.passed, Some(false));
+    }
+
+    #[test]
+    fn constant_series_is_not_applicable() {
+        let values = [42.0; 12];
+        assert_eq!(runs_test(&values).p_value, None);
+        assert_eq!(serial_correlation(&values).p_value, None);
+        assert_eq!(serial_correlation(&values).passed, None);
+    }
+
+    #[test]
+    fn suite_runs_one_chi_square_per_position() {
+        let report =
+            run_randomness_suite(&series(&[5, 17, 23, 38, 41, 59, 62, 70, 84, 96], 2)).unwrap();
+        assert_eq!(report.sample_size, 10);
+        assert_eq!(report.digit_width, 2);
+        assert_eq!(report.tests.len(), 2 + 3);
+        assert!(report.tests[0].name.contains("position 1"));
+    }
+
+    #[test]
+    fn suite_needs_ten_numbers() {
+        assert!(matches!(
+            run_randomness_suite(&series(&[1, 2, 3], 2)).err(),
+            Some(Message::InsufficientAnalysisData {
+                required: 10,
+                found: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::prizes::{prize_table_for, PrizeTier};
+use crate::i18n::Message;
+
+pub const MAX_CHECKED_TICKETS: usize = 100;
+
+/// Winning numbers of one draw per tier. Adjacent-first numbers are derived from the
+/// first prize and need not be present; a tier with no numbers is treated as unknown.
+pub struct DrawNumbers {
+    pub draw_date: Option<NaiveDate>,
+    pub numbers: BTreeMap<PrizeTier, Vec<String>>,
+}
+
+#[derive(Serialize)]
+pub struct PrizeWin {
+    pub draw_date: Option<NaiveDate>,
+    pub tier: PrizeTier,
+    pub winning_number: String,
+    /// Baht from the prize table in force on the draw date; `None` when no table covers it.
+    pub amount: Option<u64>,
+    pub prize_table: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+pub struct TicketReport {
+    pub ticket: String,
+    pub wins: Vec<PrizeWin>,
+    /// Sum of the known amounts of `wins`.
+    pub total_amount: u64,
+}
+
+#[derive(Serialize)]
+pub struct CheckReport {
+    pub draws_checked: usize,
+    pub tickets: Vec<TicketReport>,
+    /// Tiers whose winning numbers were missing from at least one checked draw, so
+    /// wins in them could not be detected.
+    pub missing_tiers: Vec<PrizeTier>,
+}
+
+/// Checks every 6-digit ticket against every draw. A ticket can win several tiers in
+/// the same draw (e.g. last-3 and last-2), as on a real ticket.
+pub fn check_tickets(tickets: &[String], draws: &[DrawNumbers]) -> Result<CheckReport, Message> {
+    let tickets: Vec<&str> = tickets.iter().map(|t| t.trim()).collect();
+    if tickets.is_empty() {
+        return Err(Message::NoTickets);
+    }
+    if tickets.len() > MAX_CHECKED_TICKETS {
+        return Err(Message::TooManyTickets {
+            max: MAX_CHECKED_TICKETS,
+        });
+    }
+    if let Some(invalid) = tickets
+        .iter()
+        .find(|t| t.len() != 6 || !t.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(Message::InvalidNumber {
+            number: invalid.to_string(),
+            width: 6,
+        });
+    }
+
+    let mut missing_tiers = BTreeSet::new();
+    let draws: Vec<(&DrawNumbers, BTreeMap<PrizeTier, Vec<String>>)> = draws
+        .iter()
+        .map(|draw| {
+            let mut numbers = draw.numbers.clone();
+            let adjacent = numbers
+                .get(&PrizeTier::FirstPrize)
+                .and_then(|first| first.first())
+                .map_or_else(Vec::new, |first| adjacent_numbers(first));
+            numbers.insert(PrizeTier::AdjacentFirst, adjacent);
+            for tier in PrizeTier::ALL {
+                if numbers.get(&tier).is_none_or(Vec::is_empty) {
+                    missing_tiers.insert(tier);
+                }
+            }
+            (draw, numbers)
+        })
+        .collect();
+
+    let tickets = tickets
+        .into_iter()
+        .map(|ticket| {
+            let mut wins = Vec::new();
+            for (draw, numbers) in &draws {
+                let table = prize_table_for(draw.draw_date);
+                for (&tier, winning) in numbers {
+                    let compared = match tier {
+                        PrizeTier::Front3 => &ticket[..3],
+                        PrizeTier::Last3 => &ticket[3..],
+                        PrizeTier::Last2 => &ticket[4..],
+                        _ => ticket,
+                    };
+                    for number in winning.iter().filter(|n| n.trim() == compared) {
+                        wins.push(PrizeWin {
+                            draw_date: draw.draw_date,
+                            tier,
+                            winning_number: number.trim().to_string(),
+                            amount: table.map(|t| t.amount(tier)),
+                            prize_table: table.map(|t| t.version),
+                        });
+                    }
+                }
+            }
+            TicketReport {
+                ticket: ticket.to_string(),
+                total_amount: wins.iter().filter_map(|w| w.amount).sum(),
+                wins,
+            }
+        })
+        .collect();
+
+    Ok(CheckReport {
+        draws_checked: draws.len(),
+        tickets,
+        missing_tiers: missing_tiers.into_iter().collect(),
+    })
+}
+
+/// The numbers either side of the first prize, wrapping 000000 and 999999 around.
+fn adjacent_numbers(first_prize: &str) -> Vec<String> {
+    let first_prize = first_prize.trim();
+    match first_prize.parse::<u32>() {
+        Ok(value) if first_prize.len() == 6 => vec![
+            format!("{:06}", (value + 999_999) % 1_000_000),
+            format!("{:06}", (value + 1) % 1_000_000),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(numbers: &[(PrizeTier, &[&str])]) -> DrawNumbers {
+        DrawNumbers {
+            draw_date: NaiveDate::from_ymd_opt(2024, 1, 16),
+            numbers: numbers
+                .iter()
+                .map(|(tier, values)| (*tier, values.iter().map(|v| v.to_string()).collect()))
+                .collect(),
+        }
+    }
+
+    fn full_draw() -> DrawNumbers {
+        draw(&[
+            (PrizeTier::FirstPrize, &["123456"]),
+            (PrizeTier::SecondPrize, &["222222"]),
+            (PrizeTier::ThirdPrize, &["333333"]),
+            (PrizeTier::FourthPrize, &["444444"]),
+            (PrizeTier::FifthPrize, &["555555"]),
+            (PrizeTier::Front3, &["123", "987"]),
+            (PrizeTier::Last3, &["456", "111"]),
+            (PrizeTier::Last2, &["56"]),
+        ])
+    }
+
+    fn tiers_won(ticket: &str, draws: &[DrawNumbers]) -> Vec<PrizeTier> {
+        let report = check_tickets(&[ticket.to_string()], draws).unwrap();
+        report.tickets[0].wins.iter().map(|w| w.tier).collect()
+    }
+
+    #[test]
+    fn first_prize_also_wins_every_matching_partial_tier() {
+        let draws = [full_draw()];
+        assert_eq!(
+            tiers_won("123456", &draws),
+            vec![
+                PrizeTier::FirstPrize,
+                PrizeTier::Front3,
+                PrizeTier::Last3,
+                PrizeTier::Last2
+            ]
+        );
+        let report = check_tickets(&["123456".to_string()], &draws).unwrap();
+        assert_eq!(
+            report.tickets[0].total_amount,
+            6_000_000 + 4_000 + 4_000 + 2_000
+        );
+        assert!(report.missing_tiers.is_empty());
+    }
+
+    #[test]
+    fn matches_adjacent_and_partial_tiers() {
+        let draws = [full_draw()];
+        // Adjacent tickets share the first prize's front three digits.
+        assert_eq!(
+            tiers_won("123457", &draws),
+            vec![PrizeTier::AdjacentFirst, PrizeTier::Front3]
+        );
+        assert_eq!(tiers_won("987000", &draws), vec![PrizeTier::Front3]);
+        assert_eq!(tiers_won("000111", &draws), vec![PrizeTier::Last3]);
+        assert_eq!(tiers_won("000056", &draws), vec![PrizeTier::Last2]);
+        assert_eq!(tiers_won("555555", &draws), vec![PrizeTier::FifthPrize]);
+        assert!(tiers_won("000000", &draws).is_empty());
+    }
+
+    #[test]
+    fn adjacent_numbers_wrap_around() {
+        assert_eq!(adjacent_numbers("000000"), vec!["999999", "000001"]);
+        assert_eq!(adjacent_numbers("999999"), vec!["999998", "000000"]);
+        assert!(adjacent_numbers("12345").is_empty());
+    }
+
+    #[test]
+    fn reports_tiers_missing_from_a_draw() {
+        let draws = [draw(&[(PrizeTier::Last2, &["56"])])];
+        let report = check_tickets(&["000056".to_string()], &draws).unwrap();
+        assert_eq!(report.draws_checked, 1);
+        assert_eq!(report.tickets[0].wins[0].amount, Some(2_000));
+        assert!(report.missing_tiers.contains(&PrizeTier::FirstPrize));
+        assert!(report.missing_tiers.contains(&PrizeTier::AdjacentFirst));
+        assert!(!report.missing_tiers.contains(&PrizeTier::Last2));
+    }
+
+    #[test]
+    fn draws_before_every_prize_table_have_no_amount() {
+        let mut old = draw(&[(PrizeTier::Last2, &["56"])]);
+        old.draw_date = NaiveDate::from_ymd_opt(2010, 1, 16);
+        let report = check_tickets(&["000056".to_string()], &[old]).unwrap();
+        let win = &report.tickets[0].wins[0];
+        assert_eq!(win.amount, None);
+        assert_eq!(win.prize_table, None);
+        assert_eq!(report.tickets[0].total_amount, 0);
+    }
+
+    #[test]
+    fn rejects_invalid_tickets() {
+        let draws = [full_draw()];
+        assert!(matches!(
+            check_tickets(&[], &draws).err(),
+            Some(Message::NoTickets)
+        ));
+        let too_many = vec!["123456".to_string(); MAX_CHECKED_TICKETS + 1];
+        assert!(matches!(
+            check_tickets(&too_many, &draws).err(),
+            Some(Message::TooManyTickets { .. })
+        ));
+        assert!(matches!(
+            check_tickets(&["12345x".to_string()], &draws).err(),
+            Some(Message::InvalidNumber { width: 6, .. })
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod checker;
+pub mod prizes;
+pub mod quick_pick;
+pub mod roi;
+pub mod wheel;
@@ -0,0 +1,174 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PrizeTier {
+    FirstPrize,
+    AdjacentFirst,
+    SecondPrize,
+    ThirdPrize,
+    FourthPrize,
+    FifthPrize,
+    Front3,
+    Last3,
+    Last2,
+}
+
+impl PrizeTier {
+    pub const ALL: [PrizeTier; 9] = [
+        PrizeTier::FirstPrize,
+        PrizeTier::AdjacentFirst,
+        PrizeTier::SecondPrize,
+        PrizeTier::ThirdPrize,
+        PrizeTier::FourthPrize,
+        PrizeTier::FifthPrize,
+        PrizeTier::Front3,
+        PrizeTier::Last3,
+        PrizeTier::Last2,
+    ];
+
+    /// Digits of the ticket compared against the tier's winning numbers.
+    pub fn digits(self) -> u32 {
+        match self {
+            PrizeTier::Front3 | PrizeTier::Last3 => 3,
+            PrizeTier::Last2 => 2,
+            _ => 6,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct TierPrize {
+    pub tier: PrizeTier,
+    /// Baht paid per winning ticket.
+    pub amount: u64,
+    /// Winning numbers drawn for this tier in each draw.
+    pub winning_numbers: u32,
+}
+
+/// Prize amounts of the Government Lottery Office for draws from `effective_from` on.
+#[derive(Serialize, Debug)]
+pub struct PrizeTable {
+    pub version: &'static str,
+    pub effective_from: NaiveDate,
+    /// Price of a single ticket in baht.
+    pub ticket_price: u64,
+    pub tiers: [TierPrize; 9],
+}
+
+impl PrizeTable {
+    pub fn amount(&self, tier: PrizeTier) -> u64 {
+        self.tiers
+            .iter()
+            .find(|t| t.tier == tier)
+            .map_or(0, |t| t.amount)
+    }
+}
+
+/// Known prize tables, oldest first. Add a new entry when the GLO changes the payout
+/// structure instead of editing an existing one, so past draws keep their amounts.
+pub static PRIZE_TABLES: [PrizeTable; 1] = [PrizeTable {
+    version: "glo-2015",
+    // First draw with the front-3 / last-3 split.
+    effective_from: match NaiveDate::from_ymd_opt(2015, 8, 16) {
+        Some(date) => date,
+        None => panic!("invalid prize table date"),
+    },
+    ticket_price: 80,
+    tiers: [
+        TierPrize {
+            tier: PrizeTier::FirstPrize,
+            amount: 6_000_000,
+            winning_numbers: 1,
+        },
+        TierPrize {
+            tier: PrizeTier::AdjacentFirst,
+            amount: 100_000,
+            winning_numbers: 2,
+        },
+        TierPrize {
+            tier: PrizeTier::SecondPrize,
+            amount: 200_000,
+            winning_numbers: 5,
+        },
+        TierPrize {
+            tier: PrizeTier::ThirdPrize,
+            amount: 80_000,
+            winning_numbers: 10,
+        },
+        TierPrize {
+            tier: PrizeTier::FourthPrize,
+            amount: 40_000,
+            winning_numbers: 50,
+        },
+        TierPrize {
+            tier: PrizeTier::FifthPrize,
+            amount: 20_000,
+            winning_numbers: 100,
+        },
+        TierPrize {
+            tier: PrizeTier::Front3,
+            amount: 4_000,
+            winning_numbers: 2,
+        },
+        TierPrize {
+            tier: PrizeTier::Last3,
+            amount: 4_000,
+            winning_numbers: 2,
+        },
+        TierPrize {
+            tier: PrizeTier::Last2,
+            amount: 2_000,
+            winning_numbers: 1,
+        },
+    ],
+}];
+
+pub fn current_prize_table() -> &'static PrizeTable {
+    PRIZE_TABLES.last().unwrap()
+}
+
+/// The table in force on `date`; undated draws use the latest table. `None` for draws
+/// older than every known table.
+pub fn prize_table_for(date: Option<NaiveDate>) -> Option<&'static PrizeTable> {
+    match date {
+        Some(date) => PRIZE_TABLES.iter().rev().find(|t| t.effective_from <= date),
+        None => PRIZE_TABLES.last(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_table_in_force_on_the_draw_date() {
+        let effective = NaiveDate::from_ymd_opt(2015, 8, 16);
+        assert_eq!(prize_table_for(effective).unwrap().version, "glo-2015");
+        assert!(prize_table_for(NaiveDate::from_ymd_opt(2015, 8, 1)).is_none());
+        assert_eq!(
+            prize_table_for(None).unwrap().version,
+            current_prize_table().version
+        );
+    }
+
+    #[test]
+    fn tables_are_ordered_and_complete() {
+        assert!(PRIZE_TABLES
+            .windows(2)
+            .all(|w| w[0].effective_from < w[1].effective_from));
+        for table in &PRIZE_TABLES {
+            for tier in PrizeTier::ALL {
+                assert!(table.amount(tier) > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn partial_tiers_compare_fewer_digits() {
+        assert_eq!(PrizeTier::FirstPrize.digits(), 6);
+        assert_eq!(PrizeTier::Front3.digits(), 3);
+        assert_eq!(PrizeTier::Last2.digits(), 2);
+    }
+}
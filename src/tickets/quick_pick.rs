@@ -0,0 +1,327 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::i18n::Message;
+
+pub const DEFAULT_TICKET_COUNT: usize = 5;
+pub const MAX_TICKET_COUNT: usize = 100;
+
+/// Random draws tried per requested ticket before the constraints are deemed unsatisfiable.
+const ATTEMPTS_PER_TICKET: usize = 20_000;
+
+#[derive(Deserialize, Default)]
+pub struct QuickPickConstraints {
+    /// Digits that must each appear at least once.
+    #[serde(default)]
+    pub include_digits: Vec<u8>,
+    /// Digits that may not appear anywhere, except where fixed by `required_positions`.
+    #[serde(default)]
+    pub exclude_digits: Vec<u8>,
+    #[serde(default)]
+    pub required_positions: Vec<RequiredDigit>,
+    /// Exact number of odd digits.
+    pub odd_count: Option<usize>,
+    pub sum_min: Option<u32>,
+    pub sum_max: Option<u32>,
+    #[serde(default)]
+    pub no_repeated_digits: bool,
+    /// Two-digit endings the ticket must finish with (e.g. the current hot numbers).
+    #[serde(default)]
+    pub allowed_endings: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RequiredDigit {
+    /// 1-based position, left to right.
+    pub position: usize,
+    pub digit: u8,
+}
+
+#[derive(Serialize)]
+pub struct QuickPickResult {
+    pub tickets: Vec<String>,
+    pub seed: Option<u64>,
+    /// Random candidates drawn to find the tickets.
+    pub attempts: usize,
+}
+
+/// Draws `count` distinct `width`-digit tickets satisfying every constraint. The same
+/// seed and constraints always yield the same tickets.
+pub fn generate_quick_picks(
+    width: usize,
+    count: usize,
+    constraints: &QuickPickConstraints,
+    seed: Option<u64>,
+) -> Result<QuickPickResult, Message> {
+    if !(1..=6).contains(&width) {
+        return Err(Message::InvalidTicketWidth);
+    }
+    let invalid_digit = constraints
+        .include_digits
+        .iter()
+        .chain(&constraints.exclude_digits)
+        .chain(constraints.required_positions.iter().map(|r| &r.digit))
+        .any(|&d| d > 9);
+    if invalid_digit {
+        return Err(Message::DigitOutOfRange);
+    }
+    if let Some(pos) = constraints
+        .required_positions
+        .iter()
+        .map(|r| r.position)
+        .find(|&p| p == 0 || p > width)
+    {
+        return Err(Message::PositionOutOfRange {
+            position: pos,
+            width,
+        });
+    }
+    let endings: BTreeSet<&str> = constraints
+        .allowed_endings
+        .iter()
+        .map(|e| e.trim())
+        .collect();
+    if !endings.is_empty() && (width < 2 || endings.iter().any(|e| !is_ending(e))) {
+        return Err(Message::InvalidEndings);
+    }
+
+    // Digits allowed at each position before the whole-ticket checks.
+    let excluded: BTreeSet<u8> = constraints.exclude_digits.iter().copied().collect();
+    let required: BTreeMap<usize, u8> = constraints
+        .required_positions
+        .iter()
+        .map(|r| (r.position, r.digit))
+        .collect();
+    let choices: Vec<Vec<u8>> = (1..=width)
+        .map(|pos| match required.get(&pos) {
+            Some(&digit) => vec![digit],
+            None => (0..10).filter(|d| !excluded.contains(d)).collect(),
+        })
+        .collect();
+    if choices.iter().any(Vec::is_empty) {
+        return Err(Message::AllDigitsExcluded);
+    }
+    let endings: Vec<[u8; 2]> = endings
+        .iter()
+        .map(|e| {
+            let b = e.as_bytes();
+            [b[0] - b'0', b[1] - b'0']
+        })
+        .filter(|[tens, units]| {
+            choices[width - 2].contains(tens) && choices[width - 1].contains(units)
+        })
+        .collect();
+    if !constraints.allowed_endings.is_empty() && endings.is_empty() {
+        return Err(Message::NoAllowedEndings);
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let count = count.clamp(1, MAX_TICKET_COUNT);
+    let max_attempts = count * ATTEMPTS_PER_TICKET;
+    let mut tickets = Vec::with_capacity(count);
+    let mut seen = BTreeSet::new();
+    let mut attempts = 0;
+    while tickets.len() < count && attempts < max_attempts {
+        attempts += 1;
+        let mut digits: Vec<u8> = choices
+            .iter()
+            .map(|options| options[rng.gen_range(0..options.len())])
+            .collect();
+        if let Some(ending) = endings.choose(&mut rng) {
+            digits[width - 2..].copy_from_slice(ending);
+        }
+        if !satisfies(&digits, constraints) {
+            continue;
+        }
+        let ticket: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+        if seen.insert(ticket.clone()) {
+            tickets.push(ticket);
+        }
+    }
+    if tickets.is_empty() {
+        return Err(Message::ConstraintsUnsatisfiable);
+    }
+
+    Ok(QuickPickResult {
+        tickets,
+        seed,
+        attempts,
+    })
+}
+
+fn is_ending(ending: &str) -> bool {
+    ending.len() == 2 && ending.chars().all(|c| c.is_ascii_digit())
+}
+
+fn satisfies(digits: &[u8], constraints: &QuickPickConstraints) -> bool {
+    if !constraints
+        .include_digits
+        .iter()
+        .all(|d| digits.contains(d))
+    {
+        return false;
+    }
+    if let Some(odd_count) = constraints.odd_count {
+        if digits.iter().filter(|&&d| d % 2 == 1).count() != odd_count {
+            return false;
+        }
+    }
+    let sum: u32 = digits.iter().map(|&d| u32::from(d)).sum();
+    if constraints.sum_min.is_some_and(|min| sum < min)
+        || constraints.sum_max.is_some_and(|max| sum > max)
+    {
+        return false;
+    }
+    if constraints.no_repeated_digits {
+        let unique: BTreeSet<&u8> = digits.iter().collect();
+        if unique.len() != digits.len() {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(ticket: &str) -> Vec<u8> {
+        ticket.bytes().map(|b| b - b'0').collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_tickets() {
+        let constraints = QuickPickConstraints::default();
+        let first = generate_quick_picks(6, 5, &constraints, Some(42)).unwrap();
+        let second = generate_quick_picks(6, 5, &constraints, Some(42)).unwrap();
+        assert_eq!(first.tickets, second.tickets);
+        assert_eq!(first.tickets.len(), 5);
+        let distinct: BTreeSet<&String> = first.tickets.iter().collect();
+        assert_eq!(distinct.len(), 5);
+        assert!(first.tickets.iter().all(|t| t.len() == 6));
+    }
+
+    #[test]
+    fn every_ticket_meets_the_constraints() {
+        let constraints = QuickPickConstraints {
+            include_digits: vec![1, 7],
+            exclude_digits: vec![0, 4],
+            required_positions: vec![RequiredDigit {
+                position: 1,
+                digit: 4,
+            }],
+            odd_count: Some(3),
+            sum_min: Some(20),
+            sum_max: Some(35),
+            no_repeated_digits: true,
+            allowed_endings: vec!["28".to_string(), "39".to_string()],
+        };
+        let result = generate_quick_picks(6, 10, &constraints, Some(7)).unwrap();
+        assert!(!result.tickets.is_empty());
+        for ticket in &result.tickets {
+            let d = digits(ticket);
+            assert_eq!(d[0], 4);
+            assert!(ticket.ends_with("28") || ticket.ends_with("39"));
+            assert!(d.contains(&1) && d.contains(&7));
+            assert!(!d[1..].contains(&0) && !d[1..].contains(&4));
+            assert_eq!(d.iter().filter(|&&x| x % 2 == 1).count(), 3);
+            let sum: u32 = d.iter().map(|&x| u32::from(x)).sum();
+            assert!((20..=35).contains(&sum));
+            assert_eq!(d.iter().collect::<BTreeSet<_>>().len(), 6);
+        }
+    }
+
+    #[test]
+    fn returns_what_exists_when_fewer_tickets_are_possible() {
+        let result =
+            generate_quick_picks(1, 20, &QuickPickConstraints::default(), Some(1)).unwrap();
+        assert_eq!(result.tickets.len(), 10);
+        assert_eq!(result.attempts, 20 * ATTEMPTS_PER_TICKET);
+    }
+
+    #[test]
+    fn rejects_invalid_constraints() {
+        let check = |width: usize, constraints: QuickPickConstraints| {
+            generate_quick_picks(width, 1, &constraints, Some(1)).err()
+        };
+        assert!(matches!(
+            check(7, QuickPickConstraints::default()),
+            Some(Message::InvalidTicketWidth)
+        ));
+        assert!(matches!(
+            check(
+                6,
+                QuickPickConstraints {
+                    include_digits: vec![10],
+                    ..Default::default()
+                }
+            ),
+            Some(Message::DigitOutOfRange)
+        ));
+        assert!(matches!(
+            check(
+                3,
+                QuickPickConstraints {
+                    required_positions: vec![RequiredDigit {
+                        position: 4,
+                        digit: 1
+                    }],
+                    ..Default::default()
+                }
+            ),
+            Some(Message::PositionOutOfRange {
+                position: 4,
+                width: 3
+            })
+        ));
+        for (width, ending) in [(6, "5"), (6, "5x"), (1, "55")] {
+            assert!(matches!(
+                check(
+                    width,
+                    QuickPickConstraints {
+                        allowed_endings: vec![ending.to_string()],
+                        ..Default::default()
+                    }
+                ),
+                Some(Message::InvalidEndings)
+            ));
+        }
+        assert!(matches!(
+            check(
+                6,
+                QuickPickConstraints {
+                    exclude_digits: (0..10).collect(),
+                    ..Default::default()
+                }
+            ),
+            Some(Message::AllDigitsExcluded)
+        ));
+        assert!(matches!(
+            check(
+                6,
+                QuickPickConstraints {
+                    exclude_digits: vec![5],
+                    allowed_endings: vec!["55".to_string()],
+                    ..Default::default()
+                }
+            ),
+            Some(Message::NoAllowedEndings)
+        ));
+        assert!(matches!(
+            check(
+                6,
+                QuickPickConstraints {
+                    sum_min: Some(55),
+                    ..Default::default()
+                }
+            ),
+            Some(Message::ConstraintsUnsatisfiable)
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::checker::{check_tickets, DrawNumbers, MAX_CHECKED_TICKETS};
+use super::prizes::{current_prize_table, prize_table_for, PrizeTier};
+use super::quick_pick::{generate_quick_picks, QuickPickConstraints};
+use crate::i18n::Message;
+use crate::stats::bayes::bayes_analysis;
+use crate::validation::{validate_numbers, ValidationConfig, ValidationPolicy};
+
+/// Draws of history the predicted strategy needs before it starts playing.
+pub const MIN_PREDICTION_HISTORY: usize = 10;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Strategy {
+    /// The same tickets every draw.
+    Fixed { tickets: Vec<String> },
+    /// `count` fresh random tickets every draw.
+    QuickPick { count: usize, seed: Option<u64> },
+    /// The top `count` Bayesian predictions for `tier`, made from the draws before each
+    /// one. Partial-ticket tiers are played with the remaining digits set to zero.
+    Predicted { tier: PrizeTier, count: usize },
+}
+
+#[derive(Serialize)]
+pub struct TierExpectation {
+    pub tier: PrizeTier,
+    pub amount: u64,
+    /// Chance that one ticket wins the tier in a uniformly random draw.
+    pub probability: f64,
+    pub expected_value: f64,
+}
+
+#[derive(Serialize)]
+pub struct ExpectedValue {
+    /// Expected winnings of one ticket in baht. Every ticket has the same expectation,
+    /// whatever strategy picked it.
+    pub per_ticket: f64,
+    pub net_per_ticket: f64,
+    /// Expected winnings as a share of the ticket price.
+    pub return_rate: f64,
+    pub tiers: Vec<TierExpectation>,
+}
+
+#[derive(Serialize)]
+pub struct TierTally {
+    pub tier: PrizeTier,
+    pub wins: usize,
+    pub amount: u64,
+}
+
+#[derive(Serialize)]
+pub struct ReplayResult {
+    /// Draws older than every known prize table are not played.
+    pub draws_played: usize,
+    pub tickets_bought: usize,
+    pub total_cost: u64,
+    pub total_winnings: u64,
+    pub net: i64,
+    /// Net result as a share of the total cost.
+    pub roi: f64,
+    pub wins: Vec<TierTally>,
+    /// Tiers missing from some replayed draws; wins in them are not counted.
+    pub missing_tiers: Vec<PrizeTier>,
+}
+
+#[derive(Serialize)]
+pub struct RoiReport {
+    pub prize_table: &'static str,
+    pub ticket_price: u64,
+    pub expected_value: ExpectedValue,
+    /// `None` when there is no draw history to replay.
+    pub replay: Option<ReplayResult>,
+}
+
+/// Theoretical expected value of a ticket under the current prize table, plus the
+/// result of replaying `strategy` over `draws` (oldest first).
+pub fn roi_analysis(
+    strategy: &Strategy,
+    ticket_price: Option<u64>,
+    draws: &[DrawNumbers],
+) -> Result<RoiReport, Message> {
+    let table = current_prize_table();
+    let ticket_price = ticket_price.unwrap_or(table.ticket_price);
+    if ticket_price == 0 {
+        return Err(Message::InvalidTicketPrice);
+    }
+
+    let tiers: Vec<TierExpectation> = table
+        .tiers
+        .iter()
+        .map(|t| {
+            let probability = t.winning_numbers as f64 / 10f64.powi(t.tier.digits() as i32);
+            TierExpectation {
+                tier: t.tier,
+                amount: t.amount,
+                probability,
+                expected_value: t.amount as f64 * probability,
+            }
+        })
+        .collect();
+    let per_ticket: f64 = tiers.iter().map(|t| t.expected_value).sum();
+    let expected_value = ExpectedValue {
+        per_ticket,
+        net_per_ticket: per_ticket - ticket_price as f64,
+        return_rate: per_ticket / ticket_price as f64,
+        tiers,
+    };
+
+    let replay = if draws.is_empty() {
+        None
+    } else {
+        Some(replay(strategy, ticket_price, draws)?)
+    };
+
+    Ok(RoiReport {
+        prize_table: table.version,
+        ticket_price,
+        expected_value,
+        replay,
+    })
+}
+
+fn replay(
+    strategy: &Strategy,
+    ticket_price: u64,
+    draws: &[DrawNumbers],
+) -> Result<ReplayResult, Message> {
+    let count = match strategy {
+        Strategy::Fixed { tickets } => tickets.len(),
+        Strategy::QuickPick { count, .. } | Strategy::Predicted { count, .. } => *count,
+    };
+    if count == 0 || count > MAX_CHECKED_TICKETS {
+        return Err(Message::TicketCountOutOfRange {
+            max: MAX_CHECKED_TICKETS,
+        });
+    }
+    if let Strategy::Predicted { tier, .. } = strategy {
+        if matches!(
+            tier,
+            PrizeTier::AdjacentFirst
+                | PrizeTier::SecondPrize
+                | PrizeTier::ThirdPrize
+                | PrizeTier::FourthPrize
+                | PrizeTier::FifthPrize
+        ) {
+            return Err(Message::UnsupportedTier);
+        }
+    }
+
+    let mut draws_in_period = 0;
+    let mut draws_played = 0;
+    let mut tickets_bought = 0;
+    let mut tallies: BTreeMap<PrizeTier, (usize, u64)> = BTreeMap::new();
+    let mut missing_tiers = BTreeSet::new();
+    for (i, draw) in draws.iter().enumerate() {
+        if prize_table_for(draw.draw_date).is_none() {
+            continue;
+        }
+        draws_in_period += 1;
+        let tickets = match strategy {
+            Strategy::Fixed { tickets } => tickets.clone(),
+            Strategy::QuickPick { count, seed } => {
+                let seed = seed.map(|s| s.wrapping_add(i as u64));
+                generate_quick_picks(6, *count, &QuickPickConstraints::default(), seed)?.tickets
+            }
+            Strategy::Predicted { tier, count } => {
+                let history: Vec<String> = draws[..i]
+                    .iter()
+                    .filter_map(|d| d.numbers.get(tier))
+                    .flatten()
+                    .cloned()
+                    .collect();
+                let config = ValidationConfig {
+                    policy: ValidationPolicy::Drop,
+                    min_size: MIN_PREDICTION_HISTORY,
+                    digit_width: Some(tier.digits() as usize),
+                };
+                let Ok(history) = validate_numbers(&history, &config) else {
+                    continue;
+                };
+                bayes_analysis(&history.numbers)?
+                    .predictions
+                    .into_iter()
+                    .take(*count)
+                    .map(|p| full_ticket(*tier, &p.number))
+                    .collect()
+            }
+        };
+        let report = check_tickets(&tickets, std::slice::from_ref(draw))?;
+        draws_played += 1;
+        tickets_bought += tickets.len();
+        missing_tiers.extend(report.missing_tiers);
+        for win in report.tickets.iter().flat_map(|t| &t.wins) {
+            let tally = tallies.entry(win.tier).or_default();
+            tally.0 += 1;
+            tally.1 += win.amount.unwrap_or(0);
+        }
+    }
+    if draws_in_period == 0 {
+        return Err(Message::NoDrawsInPrizeTablePeriod);
+    }
+    // Only the predicted strategy skips draws inside the period, while its history is short.
+    if draws_played == 0 {
+        return Err(Message::InsufficientPredictionHistory {
+            required: MIN_PREDICTION_HISTORY,
+        });
+    }
+
+    let total_cost = tickets_bought as u64 * ticket_price;
+    let total_winnings: u64 = tallies.values().map(|(_, amount)| amount).sum();
+    let net = total_winnings as i64 - total_cost as i64;
+    Ok(ReplayResult {
+        draws_played,
+        tickets_bought,
+        total_cost,
+        total_winnings,
+        net,
+        roi: net as f64 / total_cost as f64,
+        wins: tallies
+            .into_iter()
+            .map(|(tier, (wins, amount))| TierTally { tier, wins, amount })
+            .collect(),
+        missing_tiers: missing_tiers.into_iter().collect(),
+    })
+}
+
+/// A 6-digit ticket carrying `number` in the digits compared for `tier`, zeros elsewhere.
+fn full_ticket(tier: PrizeTier, number: &str) -> String {
+    match tier {
+        PrizeTier::Front3 => format!("{}000", number),
+        PrizeTier::Last3 | PrizeTier::Last2 => format!("{:0>6}", number),
+        _ => number.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn draw(date: (i32, u32, u32), first: &str, last2: &str) -> DrawNumbers {
+        DrawNumbers {
+            draw_date: NaiveDate::from_ymd_opt(date.0, date.1, date.2),
+            numbers: BTreeMap::from([
+                (PrizeTier::FirstPrize, vec![first.to_string()]),
+                (PrizeTier::Last2, vec![last2.to_string()]),
+            ]),
+        }
+    }
+
+    fn fixed(tickets: &[&str]) -> Strategy {
+        Strategy::Fixed {
+            tickets: tickets.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn expected_value_sums_every_tier() {
+        let report = roi_analysis(&fixed(&["123456"]), None, &[]).unwrap();
+        // 6 + 0.2 + 1 + 0.8 + 2 + 2 + 8 + 8 + 20 baht per 80 baht ticket.
+        assert!((report.expected_value.per_ticket - 48.0).abs() < 1e-9);
+        assert!((report.expected_value.return_rate - 0.6).abs() < 1e-9);
+        assert!((report.expected_value.net_per_ticket + 32.0).abs() < 1e-9);
+        assert!(report.replay.is_none());
+    }
+
+    #[test]
+    fn replays_fixed_tickets() {
+        let draws = [
+            draw((2020, 1, 16), "111111", "12"),
+            draw((2020, 2, 1), "123456", "99"),
+        ];
+        let report = roi_analysis(&fixed(&["000012"]), None, &draws).unwrap();
+        let replay = report.replay.unwrap();
+        assert_eq!(replay.draws_played, 2);
+        assert_eq!(replay.total_cost, 160);
+        assert_eq!(replay.total_winnings, 2_000);
+        assert_eq!(replay.net, 1_840);
+        assert_eq!(replay.wins.len(), 1);
+        assert_eq!(replay.wins[0].tier, PrizeTier::Last2);
+        assert!(replay.missing_tiers.contains(&PrizeTier::Last3));
+    }
+
+    #[test]
+    fn skips_draws_before_the_prize_table() {
+        let draws = [
+            draw((2010, 1, 16), "000012", "12"),
+            draw((2020, 1, 16), "111111", "34"),
+        ];
+        let replay = roi_analysis(&fixed(&["000012"]), None, &draws)
+            .unwrap()
+            .replay
+            .unwrap();
+        assert_eq!(replay.draws_played, 1);
+        assert_eq!(replay.total_winnings, 0);
+    }
+
+    #[test]
+    fn explains_why_nothing_was_replayed() {
+        let old = [draw((2010, 1, 16), "000012", "12")];
+        let quick_pick = Strategy::QuickPick {
+            count: 1,
+            seed: Some(1),
+        };
+        for strategy in [fixed(&["000012"]), quick_pick] {
+            assert!(matches!(
+                roi_analysis(&strategy, None, &old).err(),
+                Some(Message::NoDrawsInPrizeTablePeriod)
+            ));
+        }
+
+        let recent = [draw((2020, 1, 16), "111111", "12")];
+        let predicted = Strategy::Predicted {
+            tier: PrizeTier::Last2,
+            count: 1,
+        };
+        assert!(matches!(
+            roi_analysis(&predicted, None, &recent).err(),
+            Some(Message::InsufficientPredictionHistory { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let draws = [draw((2020, 1, 16), "111111", "12")];
+        assert!(matches!(
+            roi_analysis(&fixed(&["000012"]), Some(0), &draws).err(),
+            Some(Message::InvalidTicketPrice)
+        ));
+        assert!(matches!(
+            roi_analysis(&fixed(&[]), None, &draws).err(),
+            Some(Message::TicketCountOutOfRange { .. })
+        ));
+        let second = Strategy::Predicted {
+            tier: PrizeTier::SecondPrize,
+            count: 1,
+        };
+        assert!(matches!(
+            roi_analysis(&second, None, &draws).err(),
+            Some(Message::UnsupportedTier)
+        ));
+    }
+
+    #[test]
+    fn pads_partial_tiers_into_full_tickets() {
+        assert_eq!(full_ticket(PrizeTier::Front3, "123"), "123000");
+        assert_eq!(full_ticket(PrizeTier::Last3, "123"), "000123");
+        assert_eq!(full_ticket(PrizeTier::Last2, "07"), "000007");
+        assert_eq!(full_ticket(PrizeTier::FirstPrize, "654321"), "654321");
+    }
+}
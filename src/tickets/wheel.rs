@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use super::prizes::{current_prize_table, PrizeTier};
+use crate::i18n::Message;
+use crate::stats::permutations::unique_permutations;
+
+/// Upper bound on the expanded target numbers of one request.
+pub const MAX_WHEEL_TARGETS: usize = 1000;
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Coverage {
+    /// Each pool entry as given.
+    #[default]
+    Exact,
+    /// Every distinct ordering of the digits of each pool entry ("กลับเลข").
+    Permutations,
+    /// Pool entries are single digits; every number of the tier's width made of
+    /// distinct pool digits, in any order.
+    DigitCombinations,
+}
+
+#[derive(Deserialize)]
+pub struct WheelTarget {
+    /// One of `first_prize`, `front3`, `last3` or `last2`.
+    pub tier: PrizeTier,
+    pub pool: Vec<String>,
+    #[serde(default)]
+    pub coverage: Coverage,
+}
+
+#[derive(Serialize)]
+pub struct TierCoverage {
+    pub tier: PrizeTier,
+    /// Numbers of this tier held by at least one ticket.
+    pub numbers: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct WheelResult {
+    pub tickets: Vec<String>,
+    pub ticket_count: usize,
+    pub ticket_price: u64,
+    pub total_cost: u64,
+    pub coverage: Vec<TierCoverage>,
+}
+
+/// The fewest 6-digit tickets holding every target number in its tier. A ticket carries
+/// one front-3, one last-3 and one last-2 number, so targets of different tiers share
+/// tickets; no set smaller than the larger of the front and back requirements exists.
+pub fn generate_wheel(
+    targets: &[WheelTarget],
+    ticket_price: Option<u64>,
+) -> Result<WheelResult, Message> {
+    let ticket_price = ticket_price.unwrap_or(current_prize_table().ticket_price);
+    if ticket_price == 0 {
+        return Err(Message::InvalidTicketPrice);
+    }
+
+    let mut full = BTreeSet::new();
+    let mut front = BTreeSet::new();
+    let mut last_3 = BTreeSet::new();
+    let mut last_2 = BTreeSet::new();
+    for target in targets {
+        let set = match target.tier {
+            PrizeTier::FirstPrize => &mut full,
+            PrizeTier::Front3 => &mut front,
+            PrizeTier::Last3 => &mut last_3,
+            PrizeTier::Last2 => &mut last_2,
+            _ => return Err(Message::UnsupportedTier),
+        };
+        set.extend(expand(target)?);
+        if full.len() + front.len() + last_3.len() + last_2.len() > MAX_WHEEL_TARGETS {
+            return Err(Message::TooManyWheelTargets {
+                max: MAX_WHEEL_TARGETS,
+            });
+        }
+    }
+    if full.is_empty() && front.is_empty() && last_3.is_empty() && last_2.is_empty() {
+        return Err(Message::NoWheelTargets);
+    }
+
+    // Full tickets already hold their own front, back and ending.
+    for ticket in &full {
+        front.remove(&ticket[..3]);
+        last_3.remove(&ticket[3..]);
+        last_2.remove(&ticket[4..]);
+    }
+    for back in &last_3 {
+        last_2.remove(&back[1..]);
+    }
+    let backs: Vec<String> = last_3
+        .iter()
+        .cloned()
+        .chain(last_2.iter().map(|ending| format!("0{}", ending)))
+        .collect();
+    let fronts: Vec<String> = front.into_iter().collect();
+
+    let pairs = fronts.len().max(backs.len());
+    let pick = |parts: &[String], i: usize| {
+        parts
+            .get(i % parts.len().max(1))
+            .cloned()
+            .unwrap_or_else(|| "000".to_string())
+    };
+    let mut tickets: Vec<String> = full.into_iter().collect();
+    tickets.extend((0..pairs).map(|i| format!("{}{}", pick(&fronts, i), pick(&backs, i))));
+
+    let coverage = [
+        PrizeTier::FirstPrize,
+        PrizeTier::Front3,
+        PrizeTier::Last3,
+        PrizeTier::Last2,
+    ]
+    .into_iter()
+    .filter(|tier| targets.iter().any(|t| t.tier == *tier))
+    .map(|tier| {
+        let numbers: BTreeSet<&str> = tickets
+            .iter()
+            .map(|ticket| match tier {
+                PrizeTier::Front3 => &ticket[..3],
+                PrizeTier::Last3 => &ticket[3..],
+                PrizeTier::Last2 => &ticket[4..],
+                _ => ticket.as_str(),
+            })
+            .collect();
+        TierCoverage {
+            tier,
+            numbers: numbers.into_iter().map(str::to_string).collect(),
+        }
+    })
+    .collect();
+
+    Ok(WheelResult {
+        ticket_count: tickets.len(),
+        ticket_price,
+        total_cost: tickets.len() as u64 * ticket_price,
+        tickets,
+        coverage,
+    })
+}
+
+fn expand(target: &WheelTarget) -> Result<BTreeSet<String>, Message> {
+    let width = target.tier.digits() as usize;
+    let pool: Vec<&str> = target
+        .pool
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .collect();
+    let expected_width = match target.coverage {
+        Coverage::DigitCombinations => 1,
+        _ => width,
+    };
+    if let Some(invalid) = pool
+        .iter()
+        .find(|n| n.len() != expected_width || !n.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(Message::InvalidNumber {
+            number: invalid.to_string(),
+            width: expected_width,
+        });
+    }
+
+    // Bound the expansion before building it; duplicates only make the real count smaller.
+    let count: usize = match target.coverage {
+        Coverage::Exact => pool.len(),
+        Coverage::Permutations => pool.iter().map(|n| permutation_count(n)).sum(),
+        Coverage::DigitCombinations => {
+            let distinct = pool.iter().collect::<BTreeSet<_>>().len();
+            if distinct < width {
+                return Err(Message::NotEnoughDistinctDigits { required: width });
+            }
+            (0..width).map(|i| distinct - i).product()
+        }
+    };
+    if count > MAX_WHEEL_TARGETS {
+        return Err(Message::TooManyWheelTargets {
+            max: MAX_WHEEL_TARGETS,
+        });
+    }
+
+    Ok(match target.coverage {
+        Coverage::Exact => pool.into_iter().map(str::to_string).collect(),
+        Coverage::Permutations => pool.into_iter().flat_map(unique_permutations).collect(),
+        Coverage::DigitCombinations => {
+            let digits: Vec<char> = pool
+                .iter()
+                .flat_map(|d| d.chars())
+                .collect::<BTreeSet<char>>()
+                .into_iter()
+                .collect();
+            let mut numbers = BTreeSet::new();
+            arrangements(&digits, width, &mut String::new(), &mut numbers);
+            numbers
+        }
+    })
+}
+
+/// Distinct orderings of the digits of `number`: n! over the factorial of each digit's
+/// multiplicity.
+fn permutation_count(number: &str) -> usize {
+    let factorial = |n: usize| (1..=n).product::<usize>();
+    let mut multiplicities = [0usize; 10];
+    for b in number.bytes() {
+        multiplicities[(b - b'0') as usize] += 1;
+    }
+    multiplicities
+        .iter()
+        .fold(factorial(number.len()), |count, &m| count / factorial(m))
+}
+
+/// Every ordered selection of `width` distinct entries of `digits`.
+fn arrangements(digits: &[char], width: usize, prefix: &mut String, out: &mut BTreeSet<String>) {
+    if prefix.len() == width {
+        out.insert(prefix.clone());
+        return;
+    }
+    for &digit in digits {
+        if !prefix.contains(digit) {
+            prefix.push(digit);
+            arrangements(digits, width, prefix, out);
+            prefix.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(tier: PrizeTier, pool: &[&str], coverage: Coverage) -> WheelTarget {
+        WheelTarget {
+            tier,
+            pool: pool.iter().map(|n| n.to_string()).collect(),
+            coverage,
+        }
+    }
+
+    #[test]
+    fn front_and_back_targets_share_tickets() {
+        let targets = [
+            target(PrizeTier::Front3, &["123", "456"], Coverage::Exact),
+            target(PrizeTier::Last3, &["789"], Coverage::Exact),
+            target(PrizeTier::Last2, &["01", "02", "89"], Coverage::Exact),
+        ];
+        let wheel = generate_wheel(&targets, None).unwrap();
+        // "89" is already held by last-3 "789", leaving three backs for two fronts.
+        assert_eq!(wheel.ticket_count, 3);
+        assert_eq!(wheel.total_cost, 3 * current_prize_table().ticket_price);
+        for tier in &wheel.coverage {
+            let wanted: &[&str] = match tier.tier {
+                PrizeTier::Front3 => &["123", "456"],
+                PrizeTier::Last3 => &["789"],
+                _ => &["01", "02", "89"],
+            };
+            assert!(wanted.iter().all(|n| tier.numbers.iter().any(|c| c == n)));
+        }
+    }
+
+    #[test]
+    fn full_tickets_cover_their_own_parts() {
+        let targets = [
+            target(PrizeTier::FirstPrize, &["123456"], Coverage::Exact),
+            target(PrizeTier::Last2, &["56"], Coverage::Exact),
+        ];
+        assert_eq!(
+            generate_wheel(&targets, Some(100)).unwrap().tickets,
+            ["123456"]
+        );
+    }
+
+    #[test]
+    fn rejects_a_free_ticket() {
+        let targets = [target(PrizeTier::Last2, &["56"], Coverage::Exact)];
+        assert!(matches!(
+            generate_wheel(&targets, Some(0)).err(),
+            Some(Message::InvalidTicketPrice)
+        ));
+    }
+
+    #[test]
+    fn permutations_expand_without_duplicates() {
+        let targets = [target(PrizeTier::Last3, &["112"], Coverage::Permutations)];
+        let wheel = generate_wheel(&targets, None).unwrap();
+        assert_eq!(wheel.tickets, ["000112", "000121", "000211"]);
+    }
+
+    #[test]
+    fn digit_combinations_need_enough_distinct_digits() {
+        let targets = [target(
+            PrizeTier::Last3,
+            &["1", "2", "2"],
+            Coverage::DigitCombinations,
+        )];
+        assert!(generate_wheel(&targets, None).is_err());
+        let targets = [target(
+            PrizeTier::Last2,
+            &["1", "2", "3"],
+            Coverage::DigitCombinations,
+        )];
+        assert_eq!(generate_wheel(&targets, None).unwrap().ticket_count, 6);
+    }
+
+    #[test]
+    fn oversized_expansions_are_rejected_before_building() {
+        let pool: Vec<String> = (0..2000).map(|n| format!("{:06}", n)).collect();
+        let pool: Vec<&str> = pool.iter().map(String::as_str).collect();
+        let targets = [target(PrizeTier::FirstPrize, &pool, Coverage::Exact)];
+        assert!(generate_wheel(&targets, None).is_err());
+        // Two 6-digit numbers with distinct digits already expand to 1440 orderings.
+        let targets = [target(
+            PrizeTier::FirstPrize,
+            &["123456", "654321"],
+            Coverage::Permutations,
+        )];
+        assert!(generate_wheel(&targets, None).is_err());
+    }
+
+    #[test]
+    fn permutation_count_accounts_for_repeated_digits() {
+        assert_eq!(permutation_count("123456"), 720);
+        assert_eq!(permutation_count("112"), 3);
+        assert_eq!(permutation_count("000000"), 1);
+        assert_eq!(permutation_count("1122"), unique_permutations("1122").len());
+    }
+
+    #[test]
+    fn unsupported_tiers_and_empty_pools_are_rejected() {
+        let targets = [target(PrizeTier::SecondPrize, &["123456"], Coverage::Exact)];
+        assert!(generate_wheel(&targets, None).is_err());
+        let targets = [target(PrizeTier::Last2, &[" "], Coverage::Exact)];
+        assert!(generate_wheel(&targets, None).is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use crate::i18n::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_MIN_SIZE: usize = 10;
+/// Widest number the app handles, the six-digit first prize.
+pub const MAX_DIGIT_WIDTH: usize = 6;
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationPolicy {
+    /// Fail if any entry has a problem.
+    Reject,
+    /// Leave out problem entries and report them.
+    #[default]
+    Drop,
+    /// Zero-pad entries that lost their leading zeros; drop the rest.
+    ZeroPad,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    pub policy: ValidationPolicy,
+    /// Fewest valid numbers the analysis accepts.
+    pub min_size: usize,
+    /// Expected width in digits; the most common width in the input when omitted.
+    pub digit_width: Option<usize>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        ValidationConfig {
+            policy: ValidationPolicy::default(),
+            min_size: DEFAULT_MIN_SIZE,
+            digit_width: None,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IssueKind {
+    Empty,
+    NonDigit,
+    /// Longer than the series width.
+    MixedLength {
+        expected: usize,
+        found: usize,
+    },
+    /// Shorter than the series width, e.g. "5" for last-2 "05".
+    StrippedLeadingZeros {
+        expected: usize,
+        found: usize,
+    },
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueAction {
+    Rejected,
+    Dropped,
+    Padded,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ValidationIssue {
+    /// 0-based position in the submitted list.
+    pub index: usize,
+    pub value: String,
+    #[serde(flatten)]
+    pub kind: IssueKind,
+    pub action: IssueAction,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ValidationReport {
+    pub policy: ValidationPolicy,
+    pub digit_width: Option<usize>,
+    pub min_size: usize,
+    pub submitted: usize,
+    pub accepted: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+pub struct ValidatedNumbers {
+    pub numbers: Vec<String>,
+    pub report: ValidationReport,
+}
+
+#[derive(Debug)]
+pub enum ValidationError {
+    /// The config itself is unusable; no entry was looked at.
+    Config(Message),
+    /// The policy rejected an entry or too few numbers remain.
+    Entries(ValidationReport),
+}
+
+/// Cleans `raw` under `config.policy`. Fails with the report when the policy rejects
+/// an entry or fewer than `config.min_size` numbers remain, and without one when the
+/// digit width, given or inferred, is outside 1..=[`MAX_DIGIT_WIDTH`].
+pub fn validate_numbers(
+    raw: &[String],
+    config: &ValidationConfig,
+) -> Result<ValidatedNumbers, ValidationError> {
+    let trimmed: Vec<&str> = raw.iter().map(|s| s.trim()).collect();
+    let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let digit_width = config.digit_width.or_else(|| {
+        let mut widths: BTreeMap<usize, usize> = BTreeMap::new();
+        for s in trimmed.iter().filter(|s| is_digits(s)) {
+            *widths.entry(s.len()).or_default() += 1;
+        }
+        // Ties go to the wider width, since stripped zeros only ever shorten entries.
+        widths
+            .into_iter()
+            .max_by_key(|&(width, count)| (count, width))
+            .map(|(width, _)| width)
+    });
+    // Checked before padding, which formats at this width.
+    if digit_width.is_some_and(|width| !(1..=MAX_DIGIT_WIDTH).contains(&width)) {
+        return Err(ValidationError::Config(Message::InvalidDigitWidth {
+            max: MAX_DIGIT_WIDTH,
+        }));
+    }
+
+    let mut numbers = Vec::with_capacity(raw.len());
+    let mut issues = Vec::new();
+    for (index, value) in trimmed.iter().enumerate() {
+        let kind = if value.is_empty() {
+            IssueKind::Empty
+        } else if !is_digits(value) {
+            IssueKind::NonDigit
+        } else {
+            match digit_width {
+                Some(expected) if value.len() > expected => IssueKind::MixedLength {
+                    expected,
+                    found: value.len(),
+                },
+                Some(expected) if value.len() < expected => IssueKind::StrippedLeadingZeros {
+                    expected,
+                    found: value.len(),
+                },
+                _ => {
+                    numbers.push(value.to_string());
+                    continue;
+                }
+            }
+        };
+        let action = match (config.policy, kind) {
+            (ValidationPolicy::Reject, _) => IssueAction::Rejected,
+            (ValidationPolicy::ZeroPad, IssueKind::StrippedLeadingZeros { expected, .. }) => {
+                numbers.push(format!("{:0>width$}", value, width = expected));
+                IssueAction::Padded
+            }
+            _ => IssueAction::Dropped,
+        };
+        issues.push(ValidationIssue {
+            index,
+            value: value.to_string(),
+            kind,
+            action,
+        });
+    }
+
+    let report = ValidationReport {
+        policy: config.policy,
+        digit_width,
+        min_size: config.min_size,
+        submitted: raw.len(),
+        accepted: numbers.len(),
+        issues,
+    };
+    let rejected = report
+        .issues
+        .iter()
+        .any(|i| i.action == IssueAction::Rejected);
+    if rejected || numbers.is_empty() || numbers.len() < config.min_size {
+        return Err(ValidationError::Entries(report));
+    }
+    Ok(ValidatedNumbers { numbers, report })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn rejected_report(raw: &[String], config: &ValidationConfig) -> ValidationReport {
+        match validate_numbers(raw, config) {
+            Err(ValidationError::Entries(report)) => report,
+            _ => panic!("expected the entries to be rejected"),
+        }
+    }
+
+    fn config(policy: ValidationPolicy) -> ValidationConfig {
+        ValidationConfig {
+            policy,
+            min_size: 1,
+            digit_width: None,
+        }
+    }
+
+    #[test]
+    fn drop_policy_leaves_out_problem_entries() {
+        let raw = strings(&["05", "5", "x1", "", "123", "42"]);
+        let validated = validate_numbers(&raw, &config(ValidationPolicy::Drop)).unwrap();
+        assert_eq!(validated.numbers, ["05", "42"]);
+        let kinds: Vec<IssueKind> = validated.report.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                IssueKind::StrippedLeadingZeros {
+                    expected: 2,
+                    found: 1
+                },
+                IssueKind::NonDigit,
+                IssueKind::Empty,
+                IssueKind::MixedLength {
+                    expected: 2,
+                    found: 3
+                },
+            ]
+        );
+        assert!(validated
+            .report
+            .issues
+            .iter()
+            .all(|i| i.action == IssueAction::Dropped));
+    }
+
+    #[test]
+    fn zero_pad_policy_restores_leading_zeros_only() {
+        let raw = strings(&["05", "42", "5", "123"]);
+        let validated = validate_numbers(&raw, &config(ValidationPolicy::ZeroPad)).unwrap();
+        assert_eq!(validated.numbers, ["05", "42", "05"]);
+        assert_eq!(validated.report.issues[0].action, IssueAction::Padded);
+        assert_eq!(validated.report.issues[1].action, IssueAction::Dropped);
+    }
+
+    #[test]
+    fn reject_policy_fails_on_any_issue() {
+        let raw = strings(&["05", "42", "5"]);
+        let report = rejected_report(&raw, &config(ValidationPolicy::Reject));
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].action, IssueAction::Rejected);
+    }
+
+    #[test]
+    fn width_ties_go_to_the_wider_width() {
+        let raw = strings(&["5", "12"]);
+        let validated = validate_numbers(&raw, &config(ValidationPolicy::Drop)).unwrap();
+        assert_eq!(validated.report.digit_width, Some(2));
+        assert_eq!(validated.numbers, ["12"]);
+    }
+
+    #[test]
+    fn explicit_width_overrides_the_majority() {
+        let raw = strings(&["12", "34", "567"]);
+        let config = ValidationConfig {
+            digit_width: Some(3),
+            ..config(ValidationPolicy::Drop)
+        };
+        assert_eq!(validate_numbers(&raw, &config).unwrap().numbers, ["567"]);
+    }
+
+    #[test]
+    fn fails_below_min_size() {
+        let raw = strings(&["12", "34"]);
+        let config = ValidationConfig {
+            min_size: 3,
+            ..config(ValidationPolicy::Drop)
+        };
+        let report = rejected_report(&raw, &config);
+        assert_eq!(report.accepted, 2);
+        assert!(validate_numbers(&[], &ValidationConfig::default()).is_err());
+    }
+
+    #[test]
+    fn rejects_digit_widths_outside_the_supported_range() {
+        let raw = strings(&["12", "34"]);
+        for width in [0, MAX_DIGIT_WIDTH + 1, 70_000] {
+            let config = ValidationConfig {
+                digit_width: Some(width),
+                ..config(ValidationPolicy::ZeroPad)
+            };
+            assert!(matches!(
+                validate_numbers(&raw, &config),
+                Err(ValidationError::Config(Message::InvalidDigitWidth { .. }))
+            ));
+        }
+        let inferred = validate_numbers(&strings(&["1234567"]), &config(ValidationPolicy::Drop));
+        assert!(matches!(inferred, Err(ValidationError::Config(_))));
+    }
+}
@@ -1,6 +1,275 @@
 document.addEventListener('DOMContentLoaded', () => {
     let scrapedResultsData = []; 
 
+    // Same negotiation as the server: `?lang=en` switches to English, Thai otherwise.
+    const locale = new URLSearchParams(window.location.search).get('lang') === 'en' ? 'en' : 'th';
+    document.documentElement.lang = locale;
+
+    const labels = {
+        th: {
+            error: 'เกิดข้อผิดพลาด',
+            unknown: 'ไม่ทราบ',
+            times: count => `${count} ครั้ง`,
+            position: position => `หลักที่ ${position}`,
+            draws: count => `${count} งวด`,
+            tiers: {
+                first_prize: 'รางวัลที่ 1',
+                adjacent_first: 'รางวัลข้างเคียงรางวัลที่ 1',
+                second_prize: 'รางวัลที่ 2',
+                third_prize: 'รางวัลที่ 3',
+                fourth_prize: 'รางวัลที่ 4',
+                fifth_prize: 'รางวัลที่ 5',
+                front3: 'เลขหน้า 3 ตัว',
+                last3: 'เลขท้าย 3 ตัว',
+                last2: 'เลขท้าย 2 ตัว'
+            },
+            scrape: 'เริ่มดึงข้อมูล',
+            scraping: 'กำลังดึงข้อมูล...',
+            scrapeFailed: status => `เริ่มดึงข้อมูลไม่สำเร็จ (status: ${status})`,
+            statusFailed: 'ตรวจสอบสถานะไม่สำเร็จ',
+            noScrapeResults: 'ไม่พบข้อมูลจากการดึงข้อมูล',
+            drawColumns: ['งวดวันที่', 'รางวัลที่ 1', 'เลขหน้า 3 ตัว', 'เลขท้าย 3 ตัว', 'เลขท้าย 2 ตัว'],
+            enterNumbers: 'กรุณาใส่ชุดตัวเลขสำหรับวิเคราะห์',
+            analyze: 'เริ่มการวิเคราะห์',
+            analyzing: 'กำลังวิเคราะห์...',
+            analyzeHistory: 'วิเคราะห์จากผลรางวัลที่บันทึกไว้',
+            processing: '🧠 AI กำลังประมวลผลข้อมูล... กรุณารอสักครู่',
+            chooseFile: 'กรุณาเลือกไฟล์ที่จะนำเข้า',
+            rejects: {
+                malformed: 'อ่านข้อมูลไม่ได้',
+                missing_draw_date: 'ไม่มีวันที่ออกรางวัล',
+                missing_field: 'ขาดรางวัลที่จำเป็น',
+                invalid_numbers: 'ตัวเลขไม่ถูกต้อง',
+                duplicate_date: 'วันที่ซ้ำในไฟล์'
+            },
+            conflictActions: { replace: 'แทนที่', skip: 'คงข้อมูลเดิม' },
+            importHeading: dryRun => dryRun ? 'ผลการตรวจสอบ (ยังไม่ได้นำเข้า)' : 'นำเข้าเรียบร้อย',
+            importSummary: (report, action) => `จากทั้งหมด ${report.submitted} งวด เพิ่มใหม่ ${report.inserted.length} งวด, ซ้ำกับข้อมูลเดิม ${report.unchanged} งวด,
+                ขัดแย้ง ${report.conflicts.length} งวด (${action}), ไม่ผ่านการตรวจสอบ ${report.rejected.length} รายการ`,
+            conflictingDraws: 'งวดที่ขัดแย้ง:',
+            rejectedRecords: 'รายการที่ไม่ผ่าน:',
+            exportFailed: 'ส่งออกไม่สำเร็จ',
+            issues: {
+                empty: 'ค่าว่าง',
+                non_digit: 'มีอักขระที่ไม่ใช่ตัวเลข',
+                mixed_length: 'จำนวนหลักเกิน',
+                stripped_leading_zeros: 'เลข 0 ข้างหน้าหายไป'
+            },
+            actions: { rejected: 'ปฏิเสธ', dropped: 'ตัดทิ้ง', padded: 'เติม 0' },
+            moreIssues: count => `... และอีก ${count} รายการ`,
+            methods: { bayesian_dirichlet_multinomial: 'Bayesian Dirichlet-Multinomial Posterior' },
+            prediction: 'ผลการทำนาย',
+            confidence: 'ความเชื่อมั่น',
+            method: 'วิธีการ',
+            alternatives: 'เลขทางเลือก',
+            numericSummary: (width, max) => `สถิติเชิงตัวเลข (${width} หลัก, 0 - ${max})`,
+            numericRows: ['ค่าเฉลี่ย', 'มัธยฐาน', 'ส่วนเบี่ยงเบนมาตรฐาน', 'ความแปรปรวน', 'พิสัย', 'พิสัยระหว่างควอไทล์', 'ความเบ้', 'ความโด่งส่วนเกิน', 'สัมประสิทธิ์การแปรผัน'],
+            ofScale: percent => `${percent}% ของช่วง`,
+            categoricalSummary: 'สถิติเชิงกลุ่ม',
+            categoricalRows: ['จำนวนข้อมูล', 'ฐานนิยม', 'จำนวนค่าที่ไม่ซ้ำ', 'เอนโทรปี'],
+            bits: 'บิต',
+            patternAnalysis: 'รูปแบบที่พบ',
+            mostFrequentNumbers: 'เลขที่ออกบ่อยที่สุด',
+            digitByPosition: 'ตัวเลขที่ออกบ่อยในแต่ละหลัก',
+            topDigit: (position, digit, count) => `หลักที่ ${position}: ออกเลข '${digit}' บ่อยที่สุด (${count} ครั้ง)`,
+            explanation: 'คำอธิบายโดยละเอียด',
+            explanationRows: ['วิธีการ', 'หลักฐานทางสถิติ', 'ตรรกะการทำนาย', 'ความไม่แน่นอน'],
+            bayesTitle: mass => `การแจกแจงภายหลังแบบเบย์ (ช่วงความเชื่อมั่น ${mass}%)`,
+            bayesColumns: ['ตำแหน่ง', 'ค่าที่เป็นไปได้มากที่สุด', 'ค่าเฉลี่ยภายหลัง', 'ช่วงความเชื่อมั่น', 'กรณีสุ่มสม่ำเสมอ'],
+            inputValidation: 'การตรวจสอบข้อมูล',
+            validationUsed: (accepted, submitted, width) => `ใช้ข้อมูล ${accepted} จาก ${submitted} ชุด (${width} หลัก)`,
+            heatmapTitle: size => `แผนภาพความถี่ตัวเลข × หลัก (n = ${size})`,
+            digit: 'ตัวเลข',
+            heatCell: (count, expected, z) => `${count} / คาดหวัง ${expected} (z = ${z})`,
+            overdueTitle: 'เลขไม่ออกนาน',
+            gapColumns: ['เลข', 'ไม่ออกมาแล้ว (งวด)', 'ช่วงห่างนานที่สุด', 'ช่วงห่างเฉลี่ย', 'โอกาสของช่วงห่างนี้'],
+            allDraws: count => `ทั้งหมด (${count})`,
+            recentDraws: count => `${count} งวดล่าสุด`,
+            hotColdTitle: 'เลขร้อน / เลขเย็น',
+            hotColdColumns: ['ช่วงข้อมูล', 'เลขร้อน', 'เลขเย็น'],
+            statistics: {
+                max_frequency: 'ความถี่สูงสุดของเลขใดเลขหนึ่ง',
+                longest_gap: 'ช่วงห่างระหว่างการออกซ้ำที่ยาวที่สุด (งวด)',
+                top_digit_share: 'สัดส่วนของตัวเลขที่ออกบ่อยที่สุด'
+            },
+            monteCarloTitle: simulations => `เทียบกับข้อมูลสุ่ม Monte Carlo (${simulations} ชุด)`,
+            monteCarloColumns: ['ค่าสถิติ', 'ค่าจริง', 'ช่วง 90% ของข้อมูลสุ่ม', 'เปอร์เซ็นไทล์', 'p-value'],
+            monteCarloNote: 'ค่าที่อยู่ในช่วงของข้อมูลสุ่มแสดงว่ารูปแบบนั้นเกิดขึ้นได้ตามปกติแม้ในลำดับตัวเลขที่สุ่มล้วน',
+            markovTitle: 'แบบจำลองการเปลี่ยนสถานะมาร์คอฟ',
+            mostLikelyNext: 'เลขที่น่าจะออกถัดไป',
+            smoothing: (alpha, size) => `ปรับเรียบแบบ Laplace α = ${alpha}, n = ${size}`,
+            permutationsTitle: (prediction, count) => `กลับเลข ${prediction} (${count} แบบ)`,
+            permutationColumns: ['เลข', 'ออกในอดีต', 'ความน่าจะเป็นจากแบบจำลอง'],
+            permutationsCombined: percent => `ซื้อครบทุกแบบมีโอกาสรวม ${percent}%`,
+            realityCheck: 'ความจริงที่ควรรู้',
+            expectedValue: (price, value, rate) => `สลากราคา ${price} บาท มีมูลค่าคาดหวัง ${value} บาท (คืนทุน ${rate}%) ไม่ว่าจะเลือกเลขด้วยวิธีใด`,
+            replay: (draws, cost, winnings, roi) => `ถ้าซื้อเลขที่ทำนายงวดละ 1 ใบย้อนหลัง ${draws} งวด: ลงทุน ${cost} บาท ได้รางวัล ${winnings} บาท (ROI ${roi}%)`,
+            coOccurrenceTitle: draws => `ความสัมพันธ์ของตัวเลขที่ออกร่วมกัน (${draws} งวด)`,
+            coOccurrenceColumns: ['รูปแบบ', 'ที่พบ / ที่คาดหวัง', 'Lift', 'p-value'],
+            lastTwoByEnding: 'เลขท้าย 2 ตัว × หลักสุดท้ายรางวัลที่ 1',
+            lastTwoDigitsByFirstPrize: 'ตัวเลขในเลขท้าย 2 ตัว × ตัวเลขในรางวัลที่ 1',
+            consecutivePairs: 'คู่ตัวเลขที่ออกซ้ำงวดติดกัน',
+            consecutiveTriples: 'ชุด 3 ตัวที่ออกซ้ำงวดติดกัน',
+            pairedWith: (left, right) => `${left} กับ ${right}`,
+            bonferroniNote: '✅ = มีนัยสำคัญหลังปรับ Bonferroni',
+            groupings: {
+                draw_day: 'งวดวันที่ 1 กับ 16',
+                month: 'เดือน',
+                buddhist_year: 'ปี พ.ศ.',
+                schedule: 'งวดปกติกับงวดพิเศษ'
+            },
+            calendarTitle: draws => `วิเคราะห์ตามช่วงเวลา (${draws} งวด)`,
+            calendarColumns: ['การจัดกลุ่ม', 'ตัวเลขที่ออกบ่อยในแต่ละกลุ่ม', 'p-value', 'ผล'],
+            differs: '⚠️ แตกต่าง',
+            noDifference: '✅ ไม่แตกต่าง',
+            randomnessTitle: alpha => `ทดสอบความสุ่ม (α = ${alpha})`,
+            randomnessColumns: ['การทดสอบ', 'ค่าสถิติ', 'p-value', 'ผล'],
+            passed: '✅ ผ่าน',
+            failed: '❌ ไม่ผ่าน',
+            noWin: 'ไม่ถูกรางวัล',
+            missingTiers: tiers => `⚠️ ไม่มีข้อมูลของ ${tiers} ในบางงวด จึงตรวจรางวัลเหล่านี้ไม่ได้`,
+            drawsChecked: draws => `ตรวจกับ ${draws} งวด`,
+            ticketColumns: ['เลขสลาก', 'งวดวันที่', 'รางวัล', 'เงินรางวัล (บาท)'],
+            wheelSummary: (count, total, price) => `ต้องซื้อ ${count} ใบ รวม ${total} บาท (ใบละ ${price} บาท)`
+        },
+        en: {
+            error: 'Error',
+            unknown: 'Unknown',
+            times: count => `${count} times`,
+            position: position => `Position ${position}`,
+            draws: count => `${count} draws`,
+            tiers: {
+                first_prize: 'First prize',
+                adjacent_first: 'Next to first prize',
+                second_prize: 'Second prize',
+                third_prize: 'Third prize',
+                fourth_prize: 'Fourth prize',
+                fifth_prize: 'Fifth prize',
+                front3: 'Front 3 digits',
+                last3: 'Last 3 digits',
+                last2: 'Last 2 digits'
+            },
+            scrape: 'Start scraping',
+            scraping: 'Scraping...',
+            scrapeFailed: status => `Failed to start scraper (status: ${status}).`,
+            statusFailed: 'Error checking status',
+            noScrapeResults: 'The scraper found no results',
+            drawColumns: ['Draw Date', 'First Prize', 'Front 3 Digits', 'Last 3 Digits', 'Last 2 Digits'],
+            enterNumbers: 'Please enter the numbers to analyse',
+            analyze: 'Start analysis',
+            analyzing: 'Analysing...',
+            analyzeHistory: 'Analyse stored draws',
+            processing: '🧠 Processing the data... please wait',
+            chooseFile: 'Please choose a file to import',
+            rejects: {
+                malformed: 'Unreadable record',
+                missing_draw_date: 'Missing draw date',
+                missing_field: 'Missing a required prize',
+                invalid_numbers: 'Invalid numbers',
+                duplicate_date: 'Date repeated in the file'
+            },
+            conflictActions: { replace: 'replaced', skip: 'kept existing' },
+            importHeading: dryRun => dryRun ? 'Preview (nothing imported yet)' : 'Import complete',
+            importSummary: (report, action) => `of ${report.submitted} draws, ${report.inserted.length} new, ${report.unchanged} identical to stored draws,
+                ${report.conflicts.length} conflicting (${action}), ${report.rejected.length} rejected`,
+            conflictingDraws: 'Conflicting draws:',
+            rejectedRecords: 'Rejected records:',
+            exportFailed: 'Export failed',
+            issues: {
+                empty: 'Empty value',
+                non_digit: 'Contains non-digit characters',
+                mixed_length: 'Too many digits',
+                stripped_leading_zeros: 'Leading zeros were lost'
+            },
+            actions: { rejected: 'rejected', dropped: 'dropped', padded: 'zero-padded' },
+            moreIssues: count => `... and ${count} more`,
+            methods: { bayesian_dirichlet_multinomial: 'Bayesian Dirichlet-Multinomial Posterior' },
+            prediction: 'PREDICTION',
+            confidence: 'CONFIDENCE',
+            method: 'METHOD',
+            alternatives: 'Alternative Predictions',
+            numericSummary: (width, max) => `Numeric Summary (${width} digits, 0 - ${max})`,
+            numericRows: ['Mean', 'Median', 'Std. Dev.', 'Variance', 'Range', 'Interquartile Range', 'Distribution Skewness', 'Excess Kurtosis', 'Coefficient of Variation'],
+            ofScale: percent => `${percent}% of scale`,
+            categoricalSummary: 'Categorical Summary',
+            categoricalRows: ['Dataset Size', 'Mode', 'Distinct Values', 'Entropy'],
+            bits: 'bits',
+            patternAnalysis: 'Pattern Analysis',
+            mostFrequentNumbers: 'Most Frequent Numbers',
+            digitByPosition: 'Digit & Position Analysis',
+            topDigit: (position, digit, count) => `Position ${position}: Most frequent is '${digit}' (${count} times)`,
+            explanation: 'Detailed Explanation',
+            explanationRows: ['Methodology', 'Statistical Evidence', 'Prediction Logic', 'Uncertainty Analysis'],
+            bayesTitle: mass => `Bayesian Posterior (${mass}% credible intervals)`,
+            bayesColumns: ['Field', 'Top Value', 'Posterior Mean', 'Credible Interval', 'Uniform'],
+            inputValidation: 'Input Validation',
+            validationUsed: (accepted, submitted, width) => `Used ${accepted} of ${submitted} numbers (${width} digits)`,
+            heatmapTitle: size => `Digit × Position Heatmap (n = ${size})`,
+            digit: 'Digit',
+            heatCell: (count, expected, z) => `${count} / expected ${expected} (z = ${z})`,
+            overdueTitle: 'Overdue Numbers',
+            gapColumns: ['Number', 'Draws Since Last', 'Longest Gap', 'Mean Gap', 'Chance of Gap'],
+            allDraws: count => `All (${count})`,
+            recentDraws: count => `Last ${count} draws`,
+            hotColdTitle: 'Hot / Cold Numbers',
+            hotColdColumns: ['Window', 'Hot', 'Cold'],
+            statistics: {
+                max_frequency: 'Highest frequency of any number',
+                longest_gap: 'Longest gap between repeats (draws)',
+                top_digit_share: 'Share of the most frequent digit'
+            },
+            monteCarloTitle: simulations => `Monte Carlo Baseline (${simulations} random histories)`,
+            monteCarloColumns: ['Statistic', 'Observed', 'Random 90% Range', 'Percentile', 'p-value'],
+            monteCarloNote: 'A value inside the random range means the pattern is ordinary even for purely random numbers',
+            markovTitle: 'Markov Transition Model',
+            mostLikelyNext: 'Most likely next',
+            smoothing: (alpha, size) => `Laplace smoothing α = ${alpha}, n = ${size}`,
+            permutationsTitle: (prediction, count) => `Permutations of ${prediction} (${count})`,
+            permutationColumns: ['Number', 'Past Draws', 'Model Probability'],
+            permutationsCombined: percent => `Buying every permutation wins with a combined ${percent}% chance`,
+            realityCheck: 'Reality Check',
+            expectedValue: (price, value, rate) => `A ${price} baht ticket has an expected value of ${value} baht (${rate}% return), however the number is chosen`,
+            replay: (draws, cost, winnings, roi) => `Buying the predicted number once per draw over the last ${draws} draws: spent ${cost} baht, won ${winnings} baht (ROI ${roi}%)`,
+            coOccurrenceTitle: draws => `Co-occurrence Analysis (${draws} draws)`,
+            coOccurrenceColumns: ['Pattern', 'Observed / Expected', 'Lift', 'p-value'],
+            lastTwoByEnding: 'Last 2 digits × last digit of the first prize',
+            lastTwoDigitsByFirstPrize: 'Digits of the last 2 × digits of the first prize',
+            consecutivePairs: 'Digit pairs repeated in consecutive draws',
+            consecutiveTriples: 'Digit triples repeated in consecutive draws',
+            pairedWith: (left, right) => `${left} with ${right}`,
+            bonferroniNote: '✅ = significant after Bonferroni correction',
+            groupings: {
+                draw_day: 'Draws on the 1st vs the 16th',
+                month: 'Month',
+                buddhist_year: 'Buddhist year',
+                schedule: 'Regular vs rescheduled draws'
+            },
+            calendarTitle: draws => `Seasonal & Calendar Analysis (${draws} draws)`,
+            calendarColumns: ['Grouping', 'Top Digit per Group', 'p-value', 'Result'],
+            differs: '⚠️ Differs',
+            noDifference: '✅ No difference',
+            randomnessTitle: alpha => `Randomness Tests (α = ${alpha})`,
+            randomnessColumns: ['Test', 'Statistic', 'p-value', 'Result'],
+            passed: '✅ Pass',
+            failed: '❌ Fail',
+            noWin: 'No prize',
+            missingTiers: tiers => `⚠️ Some draws have no ${tiers} results, so those prizes could not be checked`,
+            drawsChecked: draws => `Checked against ${draws} draws`,
+            ticketColumns: ['Ticket', 'Draw Date', 'Prize', 'Amount (baht)'],
+            wheelSummary: (count, total, price) => `${count} tickets for ${total} baht in total (${price} baht each)`
+        }
+    };
+    const t = (key, ...args) => {
+        const label = labels[locale][key];
+        return typeof label === 'function' ? label(...args) : label;
+    };
+    const headerRow = columns => `<tr>${columns.map(column => `<th>${column}</th>`).join('')}</tr>`;
+    // For text taken from user input, such as uploaded files, before it goes into innerHTML.
+    const escapeHtml = text => String(text).replace(/[&<>"']/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' })[c]);
+
+    // Ask the server for its messages in the page's locale.
+    const api = (url, options = {}) => fetch(url, { ...options, headers: { ...options.headers, 'Accept-Language': locale } });
+
     const scrapeBtn = document.getElementById('scrape-btn');
     const progressContainer = document.getElementById('progress-container');
     const tableContainer = document.getElementById('table-container'); 
@@ -12,35 +281,57 @@ document.addEventListener('DOMContentLoaded', () => {
     const analysisSection = document.getElementById('analysis-section');
     const numberInput = document.getElementById('number-input');
     const analyzeBtn = document.getElementById('analyze-btn');
+    const analyzeHistoryBtn = document.getElementById('analyze-history-btn');
     const analysisResultsContainer = document.getElementById('analysis-results-container');
     const predictFirstPrizeRadio = document.getElementById('predict-first-prize');
     const predictLast2DigitsRadio = document.getElementById('predict-last-2-digits');
+    const predictLast3DigitsRadio = document.getElementById('predict-last-3-digits');
     const lastPrizeLabel = document.getElementById('last-prize-label');
+    const drawExport = document.getElementById('draw-export');
+    const analysisExport = document.getElementById('analysis-export');
+    let lastAnalysisRequest = null;
+
+    const importFile = document.getElementById('import-file');
+    const importResults = document.getElementById('import-results');
+
+    const quickPickBtn = document.getElementById('quick-pick-btn');
+    const quickPickResults = document.getElementById('quick-pick-results');
+
+    const checkTicketsBtn = document.getElementById('check-tickets-btn');
+    const checkTicketsResults = document.getElementById('check-tickets-results');
+
+    const wheelBtn = document.getElementById('wheel-btn');
+    const wheelResults = document.getElementById('wheel-results');
+
+    scrapeBtn.textContent = t('scrape');
+    analyzeBtn.textContent = t('analyze');
+    analyzeHistoryBtn.textContent = t('analyzeHistory');
 
     scrapeBtn.addEventListener('click', async () => {
         const selectedType = document.querySelector('input[name="lotto_type"]:checked').value;
         scrapeBtn.disabled = true;
-        scrapeBtn.textContent = 'กำลังดึงข้อมูล...';
+        scrapeBtn.textContent = t('scraping');
         progressContainer.style.display = 'block';
         tableContainer.style.display = 'none'; 
+        drawExport.style.display = 'none';
         
         resultsBody.innerHTML = '';
         resultsHead.innerHTML = '';
         analysisSection.style.display = 'none';
         analysisResultsContainer.innerHTML = '';
         try {
-            const response = await fetch('/start-scrape', { method: 'POST', headers: { 'Content-Type': 'application/json' }, body: JSON.stringify({ lotto_type: selectedType }) });
-            if (!response.ok) { throw new Error(`Failed to start scraper (status: ${response.status}).`); }
+            const response = await api('/start-scrape', { method: 'POST', headers: { 'Content-Type': 'application/json' }, body: JSON.stringify({ lotto_type: selectedType }) });
+            if (!response.ok) { throw new Error(t('scrapeFailed', response.status)); }
             scrapeStatusInterval = setInterval(checkScrapeStatus, 2000);
         } catch (error) {
-            progressContainer.innerHTML = `<p style="color: red;">Error: ${error.message}</p>`;
+            progressContainer.innerHTML = `<p style="color: red;">${t('error')}: ${error.message}</p>`;
             resetScraperUI();
         }
     });
 
     async function checkScrapeStatus() {
         try {
-            const response = await fetch('/status');
+            const response = await api('/status');
             const data = await response.json();
             progressContainer.innerHTML = data.progress.join('<br>');
             progressContainer.scrollTop = progressContainer.scrollHeight;
@@ -50,7 +341,7 @@ document.addEventListener('DOMContentLoaded', () => {
                 resetScraperUI();
             }
         } catch (error) {
-            progressContainer.innerHTML += `<br><p style="color: red;">Error checking status: ${error.message}</p>`;
+            progressContainer.innerHTML += `<br><p style="color: red;">${t('statusFailed')}: ${error.message}</p>`;
             clearInterval(scrapeStatusInterval);
             resetScraperUI();
         }
@@ -58,21 +349,24 @@ document.addEventListener('DOMContentLoaded', () => {
     
     function displayScrapeResults(results, lottoType) {
         if (!results || results.length === 0) {
-            progressContainer.innerHTML += '<br>ไม่พบข้อมูลจากการดึงข้อมูล';
+            progressContainer.innerHTML += `<br>${t('noScrapeResults')}`;
             return;
         }
 
         scrapedResultsData = results; 
         tableContainer.style.display = 'block'; 
+        drawExport.style.display = 'flex';
         progressContainer.style.display = 'none';
         
-        resultsHead.innerHTML = `<tr><th>Draw Date</th><th>First Prize</th><th>Last 2 Digits</th></tr>`;
-        lastPrizeLabel.textContent = 'เลขท้าย 2 ตัว';
+        resultsHead.innerHTML = headerRow(t('drawColumns'));
+        lastPrizeLabel.textContent = t('tiers').last2;
         results.forEach(result => {
             const row = resultsBody.insertRow();
-            row.insertCell(0).textContent = result['Draw Date'];
+            row.insertCell(0).textContent = result['Draw Date'] || t('unknown');
             row.insertCell(1).innerHTML = `<strong>${result['First Prize']}</strong>`;
-            row.insertCell(2).innerHTML = `<strong>${result['Last 2 Digits'] || ''}</strong>`;
+            row.insertCell(2).textContent = (result['Front 3 Digits'] || []).join(', ');
+            row.insertCell(3).textContent = (result['Last 3 Digits'] || []).join(', ');
+            row.insertCell(4).innerHTML = `<strong>${result['Last 2 Digits'] || ''}</strong>`;
         });
 
         analysisSection.style.display = 'block';
@@ -82,7 +376,7 @@ document.addEventListener('DOMContentLoaded', () => {
 
     function resetScraperUI() {
         scrapeBtn.disabled = false;
-        scrapeBtn.textContent = 'เริ่มดึงข้อมูล';
+        scrapeBtn.textContent = t('scrape');
     }
 
     function updateAnalysisInput(type) {
@@ -91,15 +385,17 @@ document.addEventListener('DOMContentLoaded', () => {
         // Reverse the array to have the latest numbers at the end
         const reversedData = [...scrapedResultsData].reverse();
         reversedData.forEach(result => {
-            let prize = '';
+            let prizes = [];
             if (type === 'first_prize') {
-                prize = result['First Prize'];
+                prizes = [result['First Prize']];
+            } else if (type === 'last_3_digits') {
+                prizes = result['Last 3 Digits'] || [];
             } else if (type === 'last_2_digits') {
-                prize = result['Last 2 Digits'];
+                prizes = [result['Last 2 Digits']];
             }
-            if (prize) {
+            prizes.filter(prize => prize).forEach(prize => {
                 numbersForAnalysis.push(prize.replace(/[^0-9]/g, ''));
-            }
+            });
         });
         numberInput.value = numbersForAnalysis.join(', ');
         analysisResultsContainer.innerHTML = '';
@@ -107,6 +403,7 @@ document.addEventListener('DOMContentLoaded', () => {
 
     predictFirstPrizeRadio.addEventListener('change', () => { updateAnalysisInput('first_prize'); });
     predictLast2DigitsRadio.addEventListener('change', () => { updateAnalysisInput('last_2_digits'); });
+    predictLast3DigitsRadio.addEventListener('change', () => { updateAnalysisInput('last_3_digits'); });
     document.querySelectorAll('input[name="analysis_method"]').forEach(radio => {
         radio.addEventListener('change', () => analysisResultsContainer.innerHTML = '');
     });
@@ -115,64 +412,619 @@ document.addEventListener('DOMContentLoaded', () => {
     analyzeBtn.addEventListener('click', async () => {
         const numbersText = numberInput.value;
         if (!numbersText.trim()) {
-            alert('กรุณาใส่ชุดตัวเลขสำหรับวิเคราะห์');
+            alert(t('enterNumbers'));
             return;
         }
         const numbersArray = numbersText.split(',').map(s => s.trim()).filter(s => s);
         
         analyzeBtn.disabled = true;
-        analyzeBtn.textContent = 'กำลังวิเคราะห์...';
-        analysisResultsContainer.innerHTML = '<p style="text-align:center;">🧠 AI กำลังประมวลผลข้อมูล... กรุณารอสักครู่</p>';
+        analyzeBtn.textContent = t('analyzing');
+        analysisExport.style.display = 'none';
+        analysisResultsContainer.innerHTML = `<p style="text-align:center;">${t('processing')}</p>`;
         try {
-            const response = await fetch('/analyze', {
+            const policy = document.getElementById('validation-policy').value;
+            const requestBody = JSON.stringify({ numbers: numbersArray, validation: { policy } });
+            const response = await api('/analyze', {
                 method: 'POST',
                 headers: { 'Content-Type': 'application/json' },
-                body: JSON.stringify({ numbers: numbersArray })
+                body: requestBody
             });
             const resultData = await response.json();
-            if (resultData.error) { throw new Error(resultData.error); }
+            if (resultData.error) {
+                throw validationError(resultData);
+            }
             displayAnalysisResults(resultData);
+            lastAnalysisRequest = { url: '/analyze/export', body: requestBody };
+            analysisExport.style.display = 'flex';
+            await loadPermutations(numbersArray, resultData.prediction_output.prediction);
+            await loadRoi();
+            await loadDigitMatrix(numbersArray);
+            await loadGapAnalysis(numbersArray);
+            await loadHotCold(numbersArray);
+            await loadMonteCarlo(numbersArray);
+            await loadMarkov(numbersArray);
+            await loadCoOccurrence();
+            await loadCalendar();
+            await loadRandomnessTests(numbersArray);
         } catch (error) {
-            analysisResultsContainer.innerHTML = `<p style="color: red; text-align:center;">เกิดข้อผิดพลาด: ${error.message}</p>`;
+            analysisResultsContainer.innerHTML = `<p style="color: red; text-align:center;">${t('error')}: ${escapeHtml(error.message)}</p>${error.issuesHtml || ''}`;
         } finally {
             analyzeBtn.disabled = false;
-            analyzeBtn.textContent = 'เริ่มการวิเคราะห์';
+            analyzeBtn.textContent = t('analyze');
         }
     });
 
+    // Analyses the stored draws of the selected field and date range on the server.
+    analyzeHistoryBtn.addEventListener('click', async () => {
+        const requestBody = JSON.stringify({
+            lotto_type: document.querySelector('input[name="lotto_type"]:checked').value,
+            field: document.querySelector('input[name="prediction_type"]:checked').value,
+            from: document.getElementById('history-from').value || null,
+            to: document.getElementById('history-to').value || null,
+            validation: validationConfig()
+        });
+
+        analyzeHistoryBtn.disabled = true;
+        analyzeHistoryBtn.textContent = t('analyzing');
+        analysisExport.style.display = 'none';
+        analysisResultsContainer.innerHTML = `<p style="text-align:center;">${t('processing')}</p>`;
+        try {
+            const response = await api('/analyze-history', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: requestBody
+            });
+            const resultData = await response.json();
+            if (resultData.error) {
+                throw validationError(resultData);
+            }
+            displayAnalysisResults(resultData);
+            lastAnalysisRequest = { url: '/analyze-history/export', body: requestBody };
+            analysisExport.style.display = 'flex';
+            await loadRoi();
+        } catch (error) {
+            analysisResultsContainer.innerHTML = `<p style="color: red; text-align:center;">${t('error')}: ${escapeHtml(error.message)}</p>${error.issuesHtml || ''}`;
+        } finally {
+            analyzeHistoryBtn.disabled = false;
+            analyzeHistoryBtn.textContent = t('analyzeHistory');
+        }
+    });
+
+    async function importDraws(dryRun) {
+        const file = importFile.files[0];
+        if (!file) {
+            alert(t('chooseFile'));
+            return;
+        }
+        const format = file.name.toLowerCase().endsWith('.jsonl') ? 'jsonl' : file.name.toLowerCase().endsWith('.json') ? 'json' : 'csv';
+        const onConflict = document.getElementById('import-conflict').value;
+        try {
+            const response = await api(`/draws/import?format=${format}&dry_run=${dryRun}&on_conflict=${onConflict}`, { method: 'POST', body: file });
+            const report = await response.json();
+            if (report.error) throw new Error(report.error);
+            const conflicts = report.conflicts.map(c => `<li>${c.draw_date}: ${escapeHtml(c.fields.join(', '))}</li>`).join('');
+            const rejected = report.rejected.slice(0, 20)
+                .map(r => `<li>#${r.record}: ${t('rejects')[r.kind]}${r.field ? ` (${escapeHtml(r.field)})` : ''}${r.error ? ` - ${escapeHtml(r.error)}` : ''}</li>`)
+                .join('');
+            const conflictAction = t('conflictActions')[report.on_conflict === 'replace' ? 'replace' : 'skip'];
+            importResults.innerHTML = `
+                <p><strong>${t('importHeading', report.dry_run)}</strong>:
+                ${t('importSummary', report, conflictAction)}</p>
+                ${conflicts ? `<p>${t('conflictingDraws')}</p><ul>${conflicts}</ul>` : ''}
+                ${rejected ? `<p>${t('rejectedRecords')}</p><ul>${rejected}</ul>` : ''}`;
+            if (!report.dry_run) {
+                const status = await (await api('/status')).json();
+                displayScrapeResults(status.results, status.lotto_type);
+            }
+        } catch (error) {
+            importResults.innerHTML = `<p style="color: red;">${t('error')}: ${escapeHtml(error.message)}</p>`;
+        }
+    }
+
+    document.getElementById('import-preview-btn').addEventListener('click', () => importDraws(true));
+    document.getElementById('import-btn').addEventListener('click', () => importDraws(false));
+
+    analysisExport.querySelectorAll('button').forEach(button => {
+        button.addEventListener('click', async () => {
+            if (!lastAnalysisRequest) return;
+            const format = button.dataset.format;
+            try {
+                const response = await api(`${lastAnalysisRequest.url}?format=${format}`, {
+                    method: 'POST',
+                    headers: { 'Content-Type': 'application/json' },
+                    body: lastAnalysisRequest.body
+                });
+                if (!response.ok) { throw new Error((await response.json()).error); }
+                const url = URL.createObjectURL(await response.blob());
+                const link = document.createElement('a');
+                link.href = url;
+                link.download = `analysis.${format === 'excel_csv' ? 'csv' : format}`;
+                link.click();
+                URL.revokeObjectURL(url);
+            } catch (error) {
+                alert(`${t('exportFailed')}: ${error.message}`);
+            }
+        });
+    });
+
+    // The server's error as the message; its validation issues, already escaped, as markup.
+    function validationError(result) {
+        const error = new Error(result.error);
+        error.issuesHtml = result.validation ? validationIssuesHtml(result.validation) : '';
+        return error;
+    }
+
+    function validationIssuesHtml(report) {
+        if (report.issues.length === 0) { return ''; }
+        const items = report.issues.slice(0, 20)
+            .map(issue => `<li>#${issue.index + 1} "${escapeHtml(issue.value)}": ${t('issues')[issue.kind]} (${t('actions')[issue.action]})</li>`)
+            .join('');
+        const more = report.issues.length > 20 ? `<li>${t('moreIssues', report.issues.length - 20)}</li>` : '';
+        return `<ul>${items}${more}</ul>`;
+    }
+
     function displayAnalysisResults(data) {
-        const { statistical_summary, pattern_analysis, prediction_output, detailed_explanation } = data;
+        const { numeric_summary, categorical_summary, pattern_analysis, prediction_output, detailed_explanation, bayesian_estimate } = data;
 
         const predictionHtml = `
             <div class="result-block prediction">
-                <h3>🔮 PREDICTION</h3>
-                <div class="prediction-value">${prediction_output.PREDICTION}</div>
-                <div class="confidence">📊 CONFIDENCE: ${prediction_output.CONFIDENCE}</div>
-                <small>🧠 METHOD: ${prediction_output.METHOD}</small>
+                <h3>🔮 ${t('prediction')}</h3>
+                <div class="prediction-value">${prediction_output.prediction ?? 'N/A'}</div>
+                <div class="confidence">📊 ${t('confidence')}: ${(prediction_output.confidence * 100).toFixed(4)}%</div>
+                <small>🧠 ${t('method')}: ${t('methods')[prediction_output.method] ?? prediction_output.method}</small>
             </div>
             <div class="result-block">
-                <h3>⚡ Alternative Predictions</h3>
-                <p>${prediction_output.ALTERNATIVE_PREDICTIONS.join(', ') || 'N/A'}</p>
+                <h3>⚡ ${t('alternatives')}</h3>
+                <p>${prediction_output.alternative_predictions.join(', ') || 'N/A'}</p>
             </div>`;
 
-        const createListHtml = (title, dataObj) => {
-            const items = Object.entries(dataObj)
+        // Pairs each row label of the locale with its value.
+        const labelled = (key, values) => t(key).map((label, i) => [label, values[i]]);
+
+        const createListHtml = (title, entries) => {
+            const items = entries
                 .map(([key, value]) => `<li><strong>${key}:</strong> ${Array.isArray(value) ? value.join('<br>') : value}</li>`)
                 .join('');
             return `<div class="result-block"><h3>${title}</h3><ul>${items}</ul></div>`;
         };
 
-        const createParagraphHtml = (title, dataObj) => {
-             const items = Object.entries(dataObj)
+        const createParagraphHtml = (title, entries) => {
+             const items = entries
                 .map(([key, value]) => `<h4>${key}</h4><p>${value}</p>`)
                 .join('');
             return `<div class="result-block"><h3>${title}</h3>${items}</div>`;
         }
 
-        const statsHtml = createListHtml('📈 Statistical Summary', statistical_summary);
-        const patternsHtml = createListHtml('🔁 Pattern Analysis', pattern_analysis);
-        const explanationHtml = createParagraphHtml('📝 Detailed Explanation', detailed_explanation);
-        
-        analysisResultsContainer.innerHTML = predictionHtml + statsHtml + patternsHtml + explanationHtml;
+        const fixed = (value, digits) => (value === null || value === undefined ? 'N/A' : value.toFixed(digits));
+        const numeric = numeric_summary;
+        const statsHtml = createListHtml(`📈 ${t('numericSummary', numeric.digit_width, numeric.scale_max)}`, labelled('numericRows', [
+            `${fixed(numeric.mean, 2)} (${t('ofScale', fixed(numeric.relative_mean * 100, 1))})`,
+            fixed(numeric.median, 2),
+            fixed(numeric.std_dev, 2),
+            fixed(numeric.variance, 2),
+            `${fixed(numeric.min, 0)} - ${fixed(numeric.max, 0)}`,
+            `${fixed(numeric.iqr, 2)} (${fixed(numeric.q1, 2)} - ${fixed(numeric.q3, 2)})`,
+            fixed(numeric.skewness, 4),
+            fixed(numeric.excess_kurtosis, 4),
+            fixed(numeric.coefficient_of_variation, 4)
+        ]));
+        const categorical = categorical_summary;
+        const categoricalHtml = createListHtml(`🏷️ ${t('categoricalSummary')}`, labelled('categoricalRows', [
+            categorical.count,
+            `${categorical.mode} (${t('times', categorical.mode_count)})`,
+            `${categorical.distinct_values} / ${categorical.possible_values.toLocaleString()}`,
+            `${fixed(categorical.entropy_bits, 3)} / ${fixed(categorical.max_entropy_bits, 3)} ${t('bits')}`
+        ]));
+        const patternsHtml = createListHtml(`🔁 ${t('patternAnalysis')}`, [
+            [t('mostFrequentNumbers'), pattern_analysis.most_frequent_numbers.map(n => `${n.number} (${t('times', n.count)})`)],
+            [t('digitByPosition'), pattern_analysis.top_digit_by_position.map(p => t('topDigit', p.position, p.digit, p.count))]
+        ]);
+        const explanationHtml = createParagraphHtml(`📝 ${t('explanation')}`, labelled('explanationRows', [
+            detailed_explanation.methodology,
+            detailed_explanation.statistical_evidence,
+            detailed_explanation.prediction_logic,
+            detailed_explanation.uncertainty_analysis
+        ]));
+
+        const posteriors = bayesian_estimate.positions.length === 2 && bayesian_estimate.endings
+            ? [{ label: t('tiers').last2, posterior: bayesian_estimate.endings }]
+            : bayesian_estimate.positions.map(p => ({ label: t('position', p.position), posterior: p.posterior }));
+        const percent = value => `${(value * 100).toFixed(2)}%`;
+        const posteriorRows = posteriors.map(({ label, posterior }) => {
+            const best = posterior.categories.reduce((a, b) => (b.posterior_mean > a.posterior_mean ? b : a));
+            return `<tr><td>${label}</td><td><strong>${best.value}</strong></td><td>${percent(best.posterior_mean)}</td><td>${percent(best.lower)} – ${percent(best.upper)}</td><td>${percent(posterior.uniform_probability)}</td></tr>`;
+        }).join('');
+        const bayesHtml = `
+            <div class="result-block">
+                <h3>📐 ${t('bayesTitle', (bayesian_estimate.credible_mass * 100).toFixed(0))}</h3>
+                <table>
+                    <thead>${headerRow(t('bayesColumns'))}</thead>
+                    <tbody>${posteriorRows}</tbody>
+                </table>
+            </div>`;
+
+        const validationHtml = data.validation.issues.length === 0 ? '' : `
+            <div class="result-block">
+                <h3>⚠️ ${t('inputValidation')}</h3>
+                <p>${t('validationUsed', data.validation.accepted, data.validation.submitted, data.validation.digit_width)}</p>
+                ${validationIssuesHtml(data.validation)}
+            </div>`;
+
+        analysisResultsContainer.innerHTML = validationHtml + predictionHtml + statsHtml + categoricalHtml + patternsHtml + bayesHtml + explanationHtml;
+
+        // Offer the predictions for wheeling instead of permuting them by hand.
+        const wheelTiers = { first_prize: 'first_prize', last_3_digits: 'last3', last_2_digits: 'last2' };
+        const field = document.querySelector('input[name="prediction_type"]:checked').value;
+        document.getElementById('wheel-pool').value = [prediction_output.prediction, ...prediction_output.alternative_predictions].filter(n => n).join(', ');
+        document.getElementById('wheel-tier').value = wheelTiers[field];
+    }
+
+    function validationConfig() {
+        return { policy: document.getElementById('validation-policy').value };
+    }
+
+    async function postNumbers(url, numbersArray) {
+        const response = await api(url, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ numbers: numbersArray, validation: validationConfig() })
+        });
+        return response.json();
+    }
+
+    async function loadDigitMatrix(numbersArray) {
+        const matrix = await postNumbers('/digit-matrix', numbersArray);
+        if (matrix.error) { return; }
+
+        // Red for digits above the uniform expectation, blue for below; full colour at |z| >= 3.
+        const heatColor = z => {
+            const alpha = Math.min(Math.abs(z) / 3, 1).toFixed(2);
+            return z >= 0 ? `rgba(220, 53, 69, ${alpha})` : `rgba(24, 119, 242, ${alpha})`;
+        };
+        const headerCells = matrix.positions.map(column => `<th>${t('position', column.position)}</th>`).join('');
+        const rows = [...Array(10).keys()].map(digit => {
+            const cells = matrix.positions.map(column => {
+                const cell = column.cells[digit];
+                const title = t('heatCell', cell.count, cell.expected.toFixed(1), cell.z_score.toFixed(2));
+                return `<td style="background-color: ${heatColor(cell.z_score)}" title="${title}">${cell.percentage.toFixed(1)}%</td>`;
+            }).join('');
+            return `<tr><th>${digit}</th>${cells}</tr>`;
+        }).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🔥 ${t('heatmapTitle', matrix.sample_size)}</h3>
+                <table class="heatmap">
+                    <thead><tr><th>${t('digit')}</th>${headerCells}</tr></thead>
+                    <tbody>${rows}</tbody>
+                </table>
+            </div>`;
     }
+
+    async function loadGapAnalysis(numbersArray) {
+        const report = await postNumbers('/gaps', numbersArray);
+        if (report.error) { return; }
+
+        const overdue = (report.endings.length > 0 ? report.endings : report.positions[0].digits)
+            .slice()
+            .sort((a, b) => b.current_gap - a.current_gap)
+            .slice(0, 10);
+        const rows = overdue.map(gap => `
+            <tr>
+                <td><strong>${gap.value}</strong></td>
+                <td>${gap.current_gap}</td>
+                <td>${gap.longest_gap}</td>
+                <td>${gap.mean_gap === null ? 'N/A' : gap.mean_gap.toFixed(1)}</td>
+                <td>${(gap.current_gap_probability * 100).toFixed(2)}%</td>
+            </tr>`).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>⏳ ${t('overdueTitle')}</h3>
+                <table>
+                    <thead>${headerRow(t('gapColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+            </div>`;
+    }
+
+    async function loadHotCold(numbersArray) {
+        const report = await postNumbers('/hot-cold', numbersArray);
+        if (report.error) { return; }
+
+        const trendArrows = { rising: '↑', steady: '→', falling: '↓' };
+        const describe = entry => `${entry.value}${entry.trend ? trendArrows[entry.trend] : ''} (${entry.count})`;
+        const rows = report.windows.map(window => {
+            const entries = window.endings.length > 0 ? window.endings : window.digits;
+            const byTemperature = temperature => entries
+                .filter(entry => entry.temperature === temperature)
+                .sort((a, b) => temperature === 'hot' ? b.count - a.count : a.count - b.count)
+                .map(describe)
+                .join(', ') || '-';
+            const label = window.window === null ? t('allDraws', window.draws) : t('recentDraws', window.draws);
+            return `<tr><td>${label}</td><td>🔥 ${byTemperature('hot')}</td><td>❄️ ${byTemperature('cold')}</td></tr>`;
+        }).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🌡️ ${t('hotColdTitle')}</h3>
+                <table>
+                    <thead>${headerRow(t('hotColdColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+            </div>`;
+    }
+
+    async function loadMonteCarlo(numbersArray) {
+        const report = await postNumbers('/monte-carlo', numbersArray);
+        if (report.error) { return; }
+
+        const format = (statistic, value) => statistic === 'top_digit_share' ? `${(value * 100).toFixed(2)}%` : value.toFixed(1);
+        const rows = report.results.map(result => `
+            <tr>
+                <td>${t('statistics')[result.statistic]}</td>
+                <td><strong>${format(result.statistic, result.observed)}</strong></td>
+                <td>${format(result.statistic, result.simulated_p5)} – ${format(result.statistic, result.simulated_p95)}</td>
+                <td>${result.percentile_rank.toFixed(1)}</td>
+                <td>${result.p_value.toFixed(3)}</td>
+            </tr>`).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🎰 ${t('monteCarloTitle', report.simulations)}</h3>
+                <table>
+                    <thead>${headerRow(t('monteCarloColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+                <small>${t('monteCarloNote')}</small>
+            </div>`;
+    }
+
+    async function loadMarkov(numbersArray) {
+        const report = await postNumbers('/markov', numbersArray);
+        if (report.error) { return; }
+
+        const transitions = report.positions
+            .map(column => `${t('position', column.position)}: ${column.last_digit} → ${column.most_likely_next} (${(column.matrix[column.last_digit].probabilities[column.most_likely_next] * 100).toFixed(1)}%)`)
+            .join('<br>');
+        const predictions = report.predictions
+            .map(prediction => `<strong>${prediction.number}</strong> (${(prediction.probability * 100).toFixed(3)}%)`)
+            .join(', ');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🔀 ${t('markovTitle')}</h3>
+                <p>${transitions}</p>
+                <p>${t('mostLikelyNext')}: ${predictions}</p>
+                <small>${t('smoothing', report.smoothing, report.sample_size)}</small>
+            </div>`;
+    }
+
+    async function loadPermutations(numbersArray, prediction) {
+        const response = await api('/permutations', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ numbers: numbersArray, prediction, validation: validationConfig() })
+        });
+        const report = await response.json();
+        if (report.error) { return; }
+
+        const rows = report.permutations.map(p => `
+            <tr>
+                <td><strong>${p.number}</strong></td>
+                <td>${p.historical_count} (${(p.historical_frequency * 100).toFixed(2)}%)</td>
+                <td>${(p.model_probability * 100).toFixed(4)}%</td>
+            </tr>`).join('');
+        const predictionBlock = analysisResultsContainer.querySelector('.result-block.prediction');
+        predictionBlock.insertAdjacentHTML('afterend', `
+            <div class="result-block">
+                <h3>🔄 ${t('permutationsTitle', report.prediction, report.permutations.length)}</h3>
+                <table>
+                    <thead>${headerRow(t('permutationColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+                <small>${t('permutationsCombined', (report.combined_probability * 100).toFixed(4))}</small>
+            </div>`);
+    }
+
+    async function loadRoi() {
+        const field = document.querySelector('input[name="prediction_type"]:checked').value;
+        const tiers = { first_prize: 'first_prize', last_3_digits: 'last3', last_2_digits: 'last2' };
+        const response = await api('/roi', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ strategy: { type: 'predicted', tier: tiers[field], count: 1 } })
+        });
+        const report = await response.json();
+        if (report.error) { return; }
+
+        const ev = report.expected_value;
+        const replay = report.replay
+            ? `<p>${t('replay', report.replay.draws_played, report.replay.total_cost.toLocaleString(), report.replay.total_winnings.toLocaleString(), (report.replay.roi * 100).toFixed(1))}</p>`
+            : '';
+        const prediction = analysisResultsContainer.querySelector('.result-block.prediction');
+        prediction.insertAdjacentHTML('afterend', `
+            <div class="result-block">
+                <h3>💸 ${t('realityCheck')}</h3>
+                <p>${t('expectedValue', report.ticket_price, ev.per_ticket.toFixed(2), (ev.return_rate * 100).toFixed(1))}</p>
+                ${replay}
+            </div>`);
+    }
+
+    async function loadCoOccurrence() {
+        if (scrapedResultsData.length === 0) return;
+        const response = await api('/cooccurrence', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ draws: [...scrapedResultsData].reverse() })
+        });
+        const report = await response.json();
+        if (report.error) { return; }
+
+        const pairRows = (entries, describe) => entries.slice(0, 5).map(entry => `
+            <tr>
+                <td>${describe(entry)}</td>
+                <td>${entry.count ?? entry.recurrences} / ${entry.expected.toFixed(2)}</td>
+                <td>${entry.lift.toFixed(2)}</td>
+                <td>${entry.p_value.toFixed(4)}${entry.significant ? ' ✅' : ''}</td>
+            </tr>`).join('');
+        const section = (title, rows) => `<tr><th colspan="4">${title}</th></tr>${rows || '<tr><td colspan="4">-</td></tr>'}`;
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🔗 ${t('coOccurrenceTitle', report.draws)}</h3>
+                <table>
+                    <thead>${headerRow(t('coOccurrenceColumns'))}</thead>
+                    <tbody>
+                        ${section(t('lastTwoByEnding'), pairRows(report.last_2_by_first_prize_ending, e => t('pairedWith', e.left, `...${e.right}`)))}
+                        ${section(t('lastTwoDigitsByFirstPrize'), pairRows(report.last_2_digits_by_first_prize_digits, e => t('pairedWith', e.left, e.right)))}
+                        ${section(t('consecutivePairs'), pairRows(report.consecutive_digit_pairs, e => e.digits))}
+                        ${section(t('consecutiveTriples'), pairRows(report.consecutive_digit_triples, e => e.digits))}
+                    </tbody>
+                </table>
+                <small>${t('bonferroniNote')}</small>
+            </div>`;
+    }
+
+    async function loadCalendar() {
+        if (scrapedResultsData.length === 0) return;
+        const field = document.querySelector('input[name="prediction_type"]:checked').value;
+        const response = await api('/calendar', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ draws: scrapedResultsData, field })
+        });
+        const report = await response.json();
+        if (report.error) { return; }
+
+        const rows = report.groupings.map(result => {
+            const groups = result.groups.map(group => `${group.group}: ${group.top_digit} (${t('draws', group.draws)})`).join(', ');
+            const pValue = result.p_value === null ? 'N/A' : result.p_value.toFixed(4);
+            const verdict = result.significant === null ? '—' : (result.significant ? t('differs') : t('noDifference'));
+            return `<tr><td>${t('groupings')[result.grouping]}</td><td>${groups}</td><td>${pValue}</td><td>${verdict}</td></tr>`;
+        }).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>📅 ${t('calendarTitle', report.draws)}</h3>
+                <table>
+                    <thead>${headerRow(t('calendarColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+            </div>`;
+    }
+
+    async function loadRandomnessTests(numbersArray) {
+        const report = await postNumbers('/randomness', numbersArray);
+        if (report.error) { return; }
+
+        const rows = report.tests.map(test => {
+            const pValue = test.p_value === null ? 'N/A' : test.p_value.toFixed(4);
+            const verdict = test.passed === null ? '—' : (test.passed ? t('passed') : t('failed'));
+            return `<tr><td>${test.name}</td><td>${test.statistic.toFixed(4)}</td><td>${pValue}</td><td>${verdict}</td></tr>`;
+        }).join('');
+        analysisResultsContainer.innerHTML += `
+            <div class="result-block">
+                <h3>🎲 ${t('randomnessTitle', report.alpha)}</h3>
+                <table>
+                    <thead>${headerRow(t('randomnessColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+            </div>`;
+    }
+
+    quickPickBtn.addEventListener('click', async () => {
+        const digitList = id => document.getElementById(id).value.split(',').map(s => s.trim()).filter(s => s).map(Number);
+        const optionalNumber = id => {
+            const value = document.getElementById(id).value;
+            return value === '' ? null : Number(value);
+        };
+        const mustEndInHot = document.getElementById('qp-hot').checked;
+        const history = [...scrapedResultsData].reverse().map(result => result['Last 2 Digits']).filter(prize => prize);
+        const request = {
+            digits: Number(document.getElementById('qp-digits').value),
+            count: optionalNumber('qp-count'),
+            seed: optionalNumber('qp-seed'),
+            include_digits: digitList('qp-include'),
+            exclude_digits: digitList('qp-exclude'),
+            odd_count: optionalNumber('qp-odd'),
+            sum_min: optionalNumber('qp-sum-min'),
+            sum_max: optionalNumber('qp-sum-max'),
+            no_repeated_digits: document.getElementById('qp-no-repeat').checked,
+            must_end_in_hot: mustEndInHot,
+            numbers: mustEndInHot ? history : []
+        };
+
+        quickPickBtn.disabled = true;
+        try {
+            const response = await api('/generate', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(request)
+            });
+            const result = await response.json();
+            if (result.error) { throw new Error(result.error); }
+            quickPickResults.innerHTML = result.tickets.join('<br>');
+        } catch (error) {
+            quickPickResults.innerHTML = `<p style="color: red; font-size: 16px;">${t('error')}: ${escapeHtml(error.message)}</p>`;
+        } finally {
+            quickPickBtn.disabled = false;
+        }
+    });
+
+    checkTicketsBtn.addEventListener('click', async () => {
+        const tickets = document.getElementById('ct-tickets').value.split(/[\s,]+/).filter(s => s);
+        const drawDate = document.getElementById('ct-date').value;
+        checkTicketsBtn.disabled = true;
+        try {
+            const response = await api('/check-tickets', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ tickets, draw_date: drawDate || null })
+            });
+            const report = await response.json();
+            if (report.error) { throw new Error(report.error); }
+            const tierNames = t('tiers');
+            const rows = report.tickets.flatMap(ticket => ticket.wins.length === 0
+                ? [`<tr><td>${escapeHtml(ticket.ticket)}</td><td colspan="3">${t('noWin')}</td></tr>`]
+                : ticket.wins.map(win => `
+                    <tr>
+                        <td>${escapeHtml(ticket.ticket)}</td>
+                        <td>${win.draw_date || '-'}</td>
+                        <td>${tierNames[win.tier]}</td>
+                        <td>${win.amount === null ? '-' : win.amount.toLocaleString()}</td>
+                    </tr>`)).join('');
+            const missing = report.missing_tiers.length === 0 ? '' :
+                `<p style="font-size: 14px;">${t('missingTiers', report.missing_tiers.map(tier => tierNames[tier]).join(', '))}</p>`;
+            checkTicketsResults.innerHTML = `
+                <p>${t('drawsChecked', report.draws_checked)}</p>
+                <table>
+                    <thead>${headerRow(t('ticketColumns'))}</thead>
+                    <tbody>${rows}</tbody>
+                </table>
+                ${missing}`;
+        } catch (error) {
+            checkTicketsResults.innerHTML = `<p style="color: red;">${t('error')}: ${escapeHtml(error.message)}</p>`;
+        } finally {
+            checkTicketsBtn.disabled = false;
+        }
+    });
+
+    wheelBtn.addEventListener('click', async () => {
+        const target = {
+            tier: document.getElementById('wheel-tier').value,
+            pool: document.getElementById('wheel-pool').value.split(/[\s,]+/).filter(s => s),
+            coverage: document.getElementById('wheel-coverage').value
+        };
+        wheelBtn.disabled = true;
+        try {
+            const response = await api('/wheel', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ targets: [target] })
+            });
+            const result = await response.json();
+            if (result.error) { throw new Error(result.error); }
+            wheelResults.innerHTML = `
+                <p>${t('wheelSummary', result.ticket_count, result.total_cost.toLocaleString(), result.ticket_price)}</p>
+                <p style="letter-spacing: 2px;">${result.tickets.join(', ')}</p>`;
+        } catch (error) {
+            wheelResults.innerHTML = `<p style="color: red;">${t('error')}: ${escapeHtml(error.message)}</p>`;
+        } finally {
+            wheelBtn.disabled = false;
+        }
+    });
 });
\ No newline at end of file